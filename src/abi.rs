@@ -0,0 +1,1363 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use clap::Parser;
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde_json::{Map, Value as JsonValue};
+use starknet::{
+    core::{
+        codec::Encode,
+        types::{
+            contract::{AbiEntry, AbiEnum, AbiFunction, AbiNamedMember, AbiStruct, SierraClass},
+            BlockId, ByteArray, ContractClass, Felt,
+        },
+        utils::{cairo_short_string_to_felt, get_selector_from_name, parse_cairo_short_string},
+    },
+    providers::Provider,
+};
+
+use crate::{
+    chain_id::ChainIdSource, decode::FeltDecoder, error::provider_error_mapper,
+    path::ExpandedPathbufParser, starknet_id::ContractCaller,
+};
+
+#[derive(Debug, Clone, Parser)]
+pub struct AbiArgs {
+    #[clap(
+        long,
+        help = "Encode arguments as typed values based on the contract ABI. \
+        See documentation for more details"
+    )]
+    typed: bool,
+    #[clap(
+        long,
+        value_parser = ExpandedPathbufParser,
+        help = "Path to the Sierra class file to take the ABI from instead of fetching the class \
+        from the network. Implies --typed"
+    )]
+    abi: Option<PathBuf>,
+}
+
+/// ABI of a Sierra class, used for encoding typed values.
+#[derive(Debug)]
+pub struct ContractAbi {
+    entries: Vec<AbiEntry>,
+}
+
+/// Cairo types relevant to serialization, parsed from ABI type names.
+#[derive(Debug)]
+enum AbiType {
+    Felt,
+    Bool,
+    Unsigned(u32),
+    Signed(u32),
+    U256,
+    ContractAddress,
+    ByteArray,
+    /// A `felt252` holding a Cairo short string. Only available as an output type hint.
+    ShortString,
+    /// Both `Array` and `Span`, which share the same serialization.
+    Array(Box<AbiType>),
+    /// Tuples, including the unit type as an empty one.
+    Tuple(Vec<AbiType>),
+    /// Structs and enums defined in the ABI.
+    Named(String),
+}
+
+/// A value in the typed argument syntax, before being matched against any type.
+#[derive(Debug)]
+enum Value {
+    /// Unquoted values, which are resolved with the usual argument resolution schemes.
+    Literal(String),
+    Str(String),
+    Array(Vec<Value>),
+    Tuple(Vec<Value>),
+    Struct(Vec<(String, Value)>),
+    /// Enum variants with payloads, like `Some(1)`. Variants without payloads are literals.
+    Variant(String, Box<Value>),
+}
+
+struct ValueParser<'a> {
+    raw: &'a str,
+    position: usize,
+}
+
+impl AbiArgs {
+    pub fn is_typed(&self) -> bool {
+        self.typed || self.abi.is_some()
+    }
+
+    /// Whether the ABI is taken from a file instead of the network.
+    pub fn has_file(&self) -> bool {
+        self.abi.is_some()
+    }
+
+    /// Loads the ABI from the file if supplied, or from the class deployed at the address.
+    pub async fn load_for_contract<P>(
+        &self,
+        provider: &P,
+        block_id: BlockId,
+        contract_address: Felt,
+    ) -> Result<ContractAbi>
+    where
+        P: Provider,
+    {
+        match &self.abi {
+            Some(path) => ContractAbi::load_from(path),
+            None => ContractAbi::fetch(provider, block_id, contract_address).await,
+        }
+    }
+
+    /// Loads the ABI from the file if supplied, or from the declared class.
+    pub async fn load_for_class<P>(
+        &self,
+        provider: &P,
+        block_id: BlockId,
+        class_hash: Felt,
+    ) -> Result<ContractAbi>
+    where
+        P: Provider,
+    {
+        match &self.abi {
+            Some(path) => ContractAbi::load_from(path),
+            None => ContractAbi::from_class(
+                provider
+                    .get_class(block_id, class_hash)
+                    .await
+                    .map_err(provider_error_mapper)?,
+            ),
+        }
+    }
+}
+
+impl ContractAbi {
+    /// Fetches the ABI of the class deployed at the address.
+    pub async fn fetch<P>(provider: &P, block_id: BlockId, contract_address: Felt) -> Result<Self>
+    where
+        P: Provider,
+    {
+        Self::from_class(
+            provider
+                .get_class_at(block_id, contract_address)
+                .await
+                .map_err(provider_error_mapper)?,
+        )
+    }
+
+    /// Loads the ABI from a Sierra class artifact, or a file containing only the ABI.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+
+        if let Ok(class) = serde_json::from_str::<SierraClass>(&content) {
+            Ok(Self { entries: class.abi })
+        } else if let Ok(entries) = serde_json::from_str::<Vec<AbiEntry>>(&content) {
+            Ok(Self { entries })
+        } else {
+            Err(anyhow::anyhow!(
+                "failed to parse {} as Sierra class or ABI",
+                path.display()
+            ))
+        }
+    }
+
+    pub fn from_class(class: ContractClass) -> Result<Self> {
+        match class {
+            ContractClass::Sierra(class) => Ok(Self {
+                entries: serde_json::from_str(&class.abi)
+                    .map_err(|err| anyhow::anyhow!("unable to parse class ABI: {}", err))?,
+            }),
+            ContractClass::Legacy(_) => Err(anyhow::anyhow!(
+                "typed values are only supported for Sierra classes"
+            )),
+        }
+    }
+
+    /// Looks up the name of the function with the selector, if it's in the ABI.
+    pub fn function_name(&self, selector: Felt) -> Option<&str> {
+        self.find_function(selector)
+            .ok()
+            .map(|function| function.name.as_str())
+    }
+
+    /// Encodes typed arguments for the function with the selector.
+    pub async fn encode_function_arguments<S>(
+        &self,
+        selector: Felt,
+        args: &[String],
+        felt_decoder: &FeltDecoder<S>,
+    ) -> Result<Vec<Felt>>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        let inputs = &self.find_function(selector)?.inputs;
+        self.encode_arguments(inputs, args, felt_decoder).await
+    }
+
+    /// Decodes the return values of the function with the selector into JSON. Functions returning
+    /// a single value are decoded into the value itself, and into an array otherwise.
+    pub fn decode_function_outputs(&self, selector: Felt, data: &[Felt]) -> Result<JsonValue> {
+        let output_types = self
+            .find_function(selector)?
+            .outputs
+            .iter()
+            .map(|output| AbiType::parse(&output.r#type))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.decode_values(&output_types, data)
+    }
+
+    /// Decodes return values with comma-separated type hints instead of an ABI, such as
+    /// `u256,bytearray`.
+    pub fn decode_with_type_hints(type_hints: &str, data: &[Felt]) -> Result<JsonValue> {
+        let output_types = split_top_level(type_hints)
+            .into_iter()
+            .map(AbiType::parse_hint)
+            .collect::<Result<Vec<_>>>()?;
+
+        // Hints never refer to named types so an empty ABI is enough
+        Self { entries: vec![] }.decode_values(&output_types, data)
+    }
+
+    fn decode_values(&self, types: &[AbiType], data: &[Felt]) -> Result<JsonValue> {
+        let mut data_iter = data.iter();
+
+        let mut values = types
+            .iter()
+            .map(|abi_type| self.decode_value(abi_type, &mut data_iter))
+            .collect::<Result<Vec<_>>>()?;
+
+        let remaining = data_iter.len();
+        if remaining > 0 {
+            anyhow::bail!(
+                "{} unexpected trailing elements after decoding all values",
+                remaining
+            );
+        }
+
+        Ok(if values.len() == 1 {
+            values.remove(0)
+        } else {
+            JsonValue::Array(values)
+        })
+    }
+
+    fn decode_value(
+        &self,
+        abi_type: &AbiType,
+        data: &mut std::slice::Iter<'_, Felt>,
+    ) -> Result<JsonValue> {
+        Ok(match abi_type {
+            AbiType::Felt | AbiType::ContractAddress => {
+                JsonValue::String(format!("{:#064x}", next_felt(data)?))
+            }
+            AbiType::ShortString => JsonValue::String(parse_cairo_short_string(next_felt(data)?)?),
+            AbiType::Bool => match next_felt(data)? {
+                value if *value == Felt::ZERO => JsonValue::Bool(false),
+                value if *value == Felt::ONE => JsonValue::Bool(true),
+                value => anyhow::bail!("invalid bool value {:#x}", value),
+            },
+            AbiType::Unsigned(bits) => {
+                let value = felt_to_biguint(next_felt(data)?);
+                if value.bits() > *bits as u64 {
+                    anyhow::bail!("value {} out of range for {}", value, abi_type);
+                }
+
+                // Larger values are represented as strings as JSON numbers lose precision
+                match value.to_u64() {
+                    Some(value) if *bits <= 64 => JsonValue::from(value),
+                    _ => JsonValue::String(value.to_string()),
+                }
+            }
+            AbiType::Signed(bits) => {
+                let value = next_felt(data)?;
+
+                let (negative, magnitude) = signed_magnitude(value);
+                if !signed_in_range(negative, &magnitude, *bits) {
+                    anyhow::bail!("value {:#x} out of range for {}", value, abi_type);
+                }
+
+                let sign = if negative { "-" } else { "" };
+                match magnitude.to_i64() {
+                    Some(magnitude) if *bits <= 64 => {
+                        JsonValue::from(if negative { -magnitude } else { magnitude })
+                    }
+                    _ => JsonValue::String(format!("{}{}", sign, magnitude)),
+                }
+            }
+            AbiType::U256 => {
+                let low = felt_to_biguint(next_felt(data)?);
+                let high = felt_to_biguint(next_felt(data)?);
+                if low.bits() > 128 || high.bits() > 128 {
+                    anyhow::bail!("invalid u256 value");
+                }
+
+                JsonValue::String(((high << 128) + low).to_string())
+            }
+            AbiType::ByteArray => {
+                let word_count = felt_to_usize(next_felt(data)?)?;
+
+                let mut bytes = vec![];
+                for _ in 0..word_count {
+                    // Full words hold 31 bytes
+                    bytes.extend_from_slice(&next_felt(data)?.to_bytes_be()[1..]);
+                }
+
+                let pending_word = next_felt(data)?.to_bytes_be();
+                let pending_word_len = felt_to_usize(next_felt(data)?)?;
+                if pending_word_len > 30 {
+                    anyhow::bail!("invalid ByteArray pending word length");
+                }
+                bytes.extend_from_slice(&pending_word[(32 - pending_word_len)..]);
+
+                JsonValue::String(String::from_utf8_lossy(&bytes).into_owned())
+            }
+            AbiType::Array(element_type) => {
+                let len = felt_to_usize(next_felt(data)?)?;
+
+                JsonValue::Array(
+                    (0..len)
+                        .map(|_| self.decode_value(element_type, data))
+                        .collect::<Result<Vec<_>>>()?,
+                )
+            }
+            AbiType::Tuple(element_types) => JsonValue::Array(
+                element_types
+                    .iter()
+                    .map(|element_type| self.decode_value(element_type, data))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            AbiType::Named(name) => {
+                if let Some(abi_struct) = self.find_struct(name) {
+                    let mut members = Map::new();
+                    for member in abi_struct.members.iter() {
+                        members.insert(
+                            member.name.clone(),
+                            self.decode_value(&AbiType::parse(&member.r#type)?, data)?,
+                        );
+                    }
+
+                    JsonValue::Object(members)
+                } else if let Some(abi_enum) = self.find_enum(name) {
+                    let index = felt_to_usize(next_felt(data)?)?;
+                    let variant = abi_enum.variants.get(index).ok_or_else(|| {
+                        anyhow::anyhow!("invalid variant index {} for enum {}", index, name)
+                    })?;
+
+                    // Variants without data are represented by their names only
+                    let variant_type = AbiType::parse(&variant.r#type)?;
+                    if variant_type.is_unit() {
+                        JsonValue::String(variant.name.clone())
+                    } else {
+                        let mut object = Map::new();
+                        object.insert(
+                            variant.name.clone(),
+                            self.decode_value(&variant_type, data)?,
+                        );
+
+                        JsonValue::Object(object)
+                    }
+                } else {
+                    anyhow::bail!("type `{}` not found in ABI", name);
+                }
+            }
+        })
+    }
+
+    /// Encodes typed arguments for the constructor. Classes without constructors take none.
+    pub async fn encode_constructor_arguments<S>(
+        &self,
+        args: &[String],
+        felt_decoder: &FeltDecoder<S>,
+    ) -> Result<Vec<Felt>>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        let inputs = self
+            .entries()
+            .find_map(|entry| match entry {
+                AbiEntry::Constructor(constructor) => Some(constructor.inputs.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        self.encode_arguments(inputs, args, felt_decoder).await
+    }
+
+    async fn encode_arguments<S>(
+        &self,
+        inputs: &[AbiNamedMember],
+        args: &[String],
+        felt_decoder: &FeltDecoder<S>,
+    ) -> Result<Vec<Felt>>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        if args.len() != inputs.len() {
+            anyhow::bail!(
+                "expected {} arguments ({}) but found {}",
+                inputs.len(),
+                inputs
+                    .iter()
+                    .map(|input| input.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                args.len()
+            );
+        }
+
+        let mut calldata = vec![];
+        for (input, arg) in inputs.iter().zip(args.iter()) {
+            let invalid_arg = |err: anyhow::Error| {
+                anyhow::anyhow!("invalid value for argument `{}`: {}", input.name, err)
+            };
+
+            let value = ValueParser::parse(arg).map_err(invalid_arg)?;
+            let abi_type = AbiType::parse(&input.r#type)?;
+
+            self.encode_value(&abi_type, &value, felt_decoder, &mut calldata)
+                .await
+                .map_err(invalid_arg)?;
+        }
+
+        Ok(calldata)
+    }
+
+    async fn encode_value<S>(
+        &self,
+        abi_type: &AbiType,
+        value: &Value,
+        felt_decoder: &FeltDecoder<S>,
+        output: &mut Vec<Felt>,
+    ) -> Result<()>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        match (abi_type, value) {
+            (AbiType::Felt | AbiType::ShortString, Value::Str(short_string)) => {
+                output.push(cairo_short_string_to_felt(short_string)?);
+            }
+            (AbiType::Felt | AbiType::ShortString, Value::Literal(literal)) => {
+                output.push(single_felt(felt_decoder.decode(literal).await?)?);
+            }
+            (AbiType::ContractAddress, Value::Literal(literal)) => {
+                output.push(
+                    felt_decoder
+                        .decode_single_with_addr_fallback(literal)
+                        .await?,
+                );
+            }
+            (AbiType::Bool, Value::Literal(literal)) => output.push(match literal.as_str() {
+                "true" => Felt::ONE,
+                "false" => Felt::ZERO,
+                _ => anyhow::bail!("invalid bool value \"{}\"", literal),
+            }),
+            (AbiType::Unsigned(bits), Value::Literal(literal)) => {
+                let value = single_felt(felt_decoder.decode(literal).await?)?;
+                if felt_to_biguint(&value).bits() > *bits as u64 {
+                    anyhow::bail!("value {} out of range for {}", literal, abi_type);
+                }
+
+                output.push(value);
+            }
+            (AbiType::Signed(bits), Value::Literal(literal)) => {
+                // Magnitudes are decoded the same way as unsigned values, while other values might
+                // already be negated by the decoder (e.g. `i8:-1`).
+                let (negative, magnitude) = match literal.strip_prefix('-') {
+                    Some(magnitude) => (
+                        true,
+                        felt_to_biguint(&single_felt(felt_decoder.decode(magnitude).await?)?),
+                    ),
+                    None => signed_magnitude(&single_felt(felt_decoder.decode(literal).await?)?),
+                };
+
+                if !signed_in_range(negative, &magnitude, *bits) {
+                    anyhow::bail!("value {} out of range for {}", literal, abi_type);
+                }
+
+                let magnitude = biguint_to_felt(&magnitude);
+                output.push(if negative {
+                    Felt::ZERO - magnitude
+                } else {
+                    magnitude
+                });
+            }
+            (AbiType::U256, Value::Literal(literal)) => {
+                let mut decoded = felt_decoder.decode(literal).await?;
+                match decoded.len() {
+                    1 => {
+                        let value = felt_to_biguint(&decoded[0]);
+                        let u128_max_plus_1 = BigUint::from(1u32) << 128;
+
+                        output.push(biguint_to_felt(&(&value % &u128_max_plus_1)));
+                        output.push(biguint_to_felt(&(&value / &u128_max_plus_1)));
+                    }
+                    2 => output.append(&mut decoded),
+                    _ => anyhow::bail!("invalid u256 value \"{}\"", literal),
+                }
+            }
+            (AbiType::U256, Value::Struct(members)) => {
+                for member in ["low", "high"] {
+                    let member_value = find_member(members, member, "u256")?;
+                    Box::pin(self.encode_value(
+                        &AbiType::Unsigned(128),
+                        member_value,
+                        felt_decoder,
+                        output,
+                    ))
+                    .await?;
+                }
+                check_unknown_members(members, &["low", "high"], "u256")?;
+            }
+            (AbiType::ByteArray, Value::Str(string)) => {
+                ByteArray::from(string.as_bytes().to_vec()).encode(output)?;
+            }
+            (AbiType::ByteArray, Value::Literal(literal)) => {
+                if literal.starts_with("bytearray:") {
+                    output.append(&mut felt_decoder.decode(literal).await?);
+                } else if has_scheme_prefix(literal) {
+                    // Most likely meant for another scheme, which doesn't produce a `ByteArray`
+                    anyhow::bail!(
+                        "invalid ByteArray value \"{}\": only the `bytearray` scheme is \
+                        supported. Quote the value to use it as a string as is",
+                        literal
+                    );
+                } else {
+                    // Unquoted strings are accepted as quoting is cumbersome on the command line
+                    ByteArray::from(literal.as_bytes().to_vec()).encode(output)?;
+                }
+            }
+            (AbiType::Array(element_type), Value::Array(elements)) => {
+                output.push(Felt::from(elements.len()));
+                for element in elements.iter() {
+                    Box::pin(self.encode_value(element_type, element, felt_decoder, output))
+                        .await?;
+                }
+            }
+            (AbiType::Tuple(element_types), Value::Tuple(elements)) => {
+                if element_types.len() != elements.len() {
+                    anyhow::bail!(
+                        "expected tuple of {} elements but found {}",
+                        element_types.len(),
+                        elements.len()
+                    );
+                }
+
+                for (element_type, element) in element_types.iter().zip(elements.iter()) {
+                    Box::pin(self.encode_value(element_type, element, felt_decoder, output))
+                        .await?;
+                }
+            }
+            (AbiType::Named(name), value) => {
+                if let Some(abi_struct) = self.find_struct(name) {
+                    Box::pin(self.encode_struct(abi_struct, value, felt_decoder, output)).await?;
+                } else if let Some(abi_enum) = self.find_enum(name) {
+                    Box::pin(self.encode_enum(abi_enum, value, felt_decoder, output)).await?;
+                } else {
+                    anyhow::bail!("type `{}` not found in ABI", name);
+                }
+            }
+            (abi_type, _) => anyhow::bail!("unexpected value for type {}", abi_type),
+        }
+
+        Ok(())
+    }
+
+    async fn encode_struct<S>(
+        &self,
+        abi_struct: &AbiStruct,
+        value: &Value,
+        felt_decoder: &FeltDecoder<S>,
+        output: &mut Vec<Felt>,
+    ) -> Result<()>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        let members = match value {
+            Value::Struct(members) => members,
+            _ => anyhow::bail!(
+                "expected struct {} in the form of `{{ member: value, ... }}`",
+                abi_struct.name
+            ),
+        };
+
+        for member in abi_struct.members.iter() {
+            let member_value = find_member(members, &member.name, &abi_struct.name)?;
+            self.encode_value(
+                &AbiType::parse(&member.r#type)?,
+                member_value,
+                felt_decoder,
+                output,
+            )
+            .await?;
+        }
+
+        let member_names = abi_struct
+            .members
+            .iter()
+            .map(|member| member.name.as_str())
+            .collect::<Vec<_>>();
+        check_unknown_members(members, &member_names, &abi_struct.name)?;
+
+        Ok(())
+    }
+
+    async fn encode_enum<S>(
+        &self,
+        abi_enum: &AbiEnum,
+        value: &Value,
+        felt_decoder: &FeltDecoder<S>,
+        output: &mut Vec<Felt>,
+    ) -> Result<()>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        let (variant_name, payload) = match value {
+            Value::Literal(variant_name) => (variant_name, None),
+            Value::Variant(variant_name, payload) => (variant_name, Some(payload.as_ref())),
+            _ => anyhow::bail!(
+                "expected enum {} in the form of `Variant` or `Variant(value)`",
+                abi_enum.name
+            ),
+        };
+
+        let (index, variant) = abi_enum
+            .variants
+            .iter()
+            .enumerate()
+            .find(|(_, variant)| &variant.name == variant_name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown variant `{}` of enum {}",
+                    variant_name,
+                    abi_enum.name
+                )
+            })?;
+        output.push(Felt::from(index));
+
+        let variant_type = AbiType::parse(&variant.r#type)?;
+        match payload {
+            Some(payload) => {
+                self.encode_value(&variant_type, payload, felt_decoder, output)
+                    .await?
+            }
+            None if variant_type.is_unit() => {}
+            None => anyhow::bail!(
+                "variant `{}` of enum {} expects a value in the form of `{}(value)`",
+                variant_name,
+                abi_enum.name,
+                variant_name
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn find_function(&self, selector: Felt) -> Result<&AbiFunction> {
+        self.entries()
+            .find_map(|entry| match entry {
+                AbiEntry::Function(function) | AbiEntry::L1Handler(function)
+                    if get_selector_from_name(&function.name).ok() == Some(selector) =>
+                {
+                    Some(function)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!("function with selector {:#064x} not found in ABI", selector)
+            })
+    }
+
+    /// Top-level entries, with items from interfaces flattened.
+    fn entries(&self) -> impl Iterator<Item = &AbiEntry> {
+        self.entries.iter().flat_map(|entry| match entry {
+            AbiEntry::Interface(interface) => interface.items.iter().collect::<Vec<_>>(),
+            entry => vec![entry],
+        })
+    }
+
+    fn find_struct(&self, name: &str) -> Option<&AbiStruct> {
+        self.entries().find_map(|entry| match entry {
+            AbiEntry::Struct(abi_struct) if abi_struct.name == name => Some(abi_struct),
+            _ => None,
+        })
+    }
+
+    fn find_enum(&self, name: &str) -> Option<&AbiEnum> {
+        self.entries().find_map(|entry| match entry {
+            AbiEntry::Enum(abi_enum) if abi_enum.name == name => Some(abi_enum),
+            _ => None,
+        })
+    }
+}
+
+impl AbiType {
+    fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+
+        if let Some(elements) = raw.strip_prefix('(') {
+            let elements = elements
+                .strip_suffix(')')
+                .ok_or_else(|| anyhow::anyhow!("invalid tuple type: {}", raw))?;
+
+            return Ok(Self::Tuple(
+                split_top_level(elements)
+                    .into_iter()
+                    .map(Self::parse)
+                    .collect::<Result<Vec<_>>>()?,
+            ));
+        }
+
+        let (path, generic_arg) = match raw.split_once("::<") {
+            Some((path, generic_arg)) => (
+                path,
+                Some(
+                    generic_arg
+                        .strip_suffix('>')
+                        .ok_or_else(|| anyhow::anyhow!("invalid generic type: {}", raw))?,
+                ),
+            ),
+            None => (raw, None),
+        };
+
+        Ok(match (path, generic_arg) {
+            ("core::felt252", None)
+            | ("core::starknet::class_hash::ClassHash", None)
+            | ("core::starknet::eth_address::EthAddress", None)
+            | ("core::starknet::storage_access::StorageAddress", None)
+            | ("core::bytes_31::bytes31", None) => Self::Felt,
+            ("core::bool", None) => Self::Bool,
+            ("core::integer::u8", None) => Self::Unsigned(8),
+            ("core::integer::u16", None) => Self::Unsigned(16),
+            ("core::integer::u32", None) | ("core::integer::usize", None) => Self::Unsigned(32),
+            ("core::integer::u64", None) => Self::Unsigned(64),
+            ("core::integer::u128", None) => Self::Unsigned(128),
+            ("core::integer::i8", None) => Self::Signed(8),
+            ("core::integer::i16", None) => Self::Signed(16),
+            ("core::integer::i32", None) => Self::Signed(32),
+            ("core::integer::i64", None) => Self::Signed(64),
+            ("core::integer::i128", None) => Self::Signed(128),
+            ("core::integer::u256", None) => Self::U256,
+            ("core::starknet::contract_address::ContractAddress", None) => Self::ContractAddress,
+            ("core::byte_array::ByteArray", None) => Self::ByteArray,
+            ("core::array::Array", Some(element)) | ("core::array::Span", Some(element)) => {
+                Self::Array(Box::new(Self::parse(element)?))
+            }
+            // Serialized the same way as the wrapped type
+            ("core::zeroable::NonZero", Some(inner)) => Self::parse(inner)?,
+            _ => Self::Named(raw.to_owned()),
+        })
+    }
+
+    /// Parses short type names used as output type hints, such as `u256` or `array<felt>`.
+    fn parse_hint(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+
+        if let Some(element) = raw
+            .strip_prefix("array<")
+            .or_else(|| raw.strip_prefix("span<"))
+        {
+            let element = element
+                .strip_suffix('>')
+                .ok_or_else(|| anyhow::anyhow!("invalid type hint: {}", raw))?;
+            return Ok(Self::Array(Box::new(Self::parse_hint(element)?)));
+        }
+
+        Ok(match raw.to_lowercase().as_str() {
+            "felt" | "felt252" => Self::Felt,
+            "str" | "shortstring" => Self::ShortString,
+            "address" | "contractaddress" => Self::ContractAddress,
+            "bool" => Self::Bool,
+            "u8" => Self::Unsigned(8),
+            "u16" => Self::Unsigned(16),
+            "u32" | "usize" => Self::Unsigned(32),
+            "u64" => Self::Unsigned(64),
+            "u128" => Self::Unsigned(128),
+            "i8" => Self::Signed(8),
+            "i16" => Self::Signed(16),
+            "i32" => Self::Signed(32),
+            "i64" => Self::Signed(64),
+            "i128" => Self::Signed(128),
+            "u256" => Self::U256,
+            "bytearray" => Self::ByteArray,
+            _ => anyhow::bail!(
+                "unknown type hint \"{}\". Supported types: felt, str, address, bool, u8 to u128, \
+                i8 to i128, u256, bytearray, array<T>",
+                raw
+            ),
+        })
+    }
+
+    fn is_unit(&self) -> bool {
+        matches!(self, Self::Tuple(elements) if elements.is_empty())
+    }
+}
+
+impl Display for AbiType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Felt => write!(f, "felt252"),
+            Self::Bool => write!(f, "bool"),
+            Self::Unsigned(bits) => write!(f, "u{}", bits),
+            Self::Signed(bits) => write!(f, "i{}", bits),
+            Self::U256 => write!(f, "u256"),
+            Self::ContractAddress => write!(f, "ContractAddress"),
+            Self::ByteArray => write!(f, "ByteArray"),
+            Self::ShortString => write!(f, "short string"),
+            Self::Array(element) => write!(f, "Array<{}>", element),
+            Self::Tuple(elements) => {
+                write!(f, "(")?;
+                for (ind, element) in elements.iter().enumerate() {
+                    if ind > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, ")")
+            }
+            Self::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl<'a> ValueParser<'a> {
+    fn parse(raw: &'a str) -> Result<Value> {
+        let mut parser = Self { raw, position: 0 };
+
+        let value = parser.parse_value()?;
+
+        parser.skip_whitespace();
+        if parser.position != raw.len() {
+            anyhow::bail!("unexpected character at position {}", parser.position);
+        }
+
+        Ok(value)
+    }
+
+    fn parse_value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('{') => {
+                self.position += 1;
+                Ok(Value::Struct(self.parse_list('}', |parser| {
+                    parser.skip_whitespace();
+                    let key = match parser.peek() {
+                        Some('"') => parser.parse_string()?,
+                        _ => parser.parse_identifier()?,
+                    };
+                    parser.expect(':')?;
+
+                    Ok((key, parser.parse_value()?))
+                })?))
+            }
+            Some('[') => {
+                self.position += 1;
+                Ok(Value::Array(self.parse_list(']', Self::parse_value)?))
+            }
+            Some('(') => {
+                self.position += 1;
+                Ok(Value::Tuple(self.parse_list(')', Self::parse_value)?))
+            }
+            Some('"') => Ok(Value::Str(self.parse_string()?)),
+            Some(_) => {
+                let literal = self.parse_literal()?;
+
+                if self.peek() == Some('(') {
+                    self.position += 1;
+                    let payload = self.parse_value()?;
+                    self.expect(')')?;
+
+                    Ok(Value::Variant(literal, Box::new(payload)))
+                } else {
+                    Ok(Value::Literal(literal))
+                }
+            }
+            None => Err(anyhow::anyhow!("unexpected end of value")),
+        }
+    }
+
+    /// Parses comma-separated items until the closing character, allowing a trailing comma.
+    fn parse_list<T, F>(&mut self, close: char, mut parse_item: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> Result<T>,
+    {
+        let mut items = vec![];
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.position += 1;
+                return Ok(items);
+            }
+
+            items.push(parse_item(self)?);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(c) if c == close => {}
+                _ => anyhow::bail!("expected `,` or `{}` at position {}", close, self.position),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String> {
+        // Skips the opening quote
+        self.position += 1;
+
+        let raw = self.raw;
+        let mut string = String::new();
+        let mut chars = raw[self.position..].chars();
+
+        loop {
+            let c = chars
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("unterminated string"))?;
+            self.position += c.len_utf8();
+
+            match c {
+                '"' => return Ok(string),
+                '\\' => {
+                    let escaped = chars
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("unterminated string"))?;
+                    self.position += escaped.len_utf8();
+
+                    string.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '"' | '\\' => escaped,
+                        _ => anyhow::bail!("unknown escape sequence `\\{}`", escaped),
+                    });
+                }
+                c => string.push(c),
+            }
+        }
+    }
+
+    fn parse_identifier(&mut self) -> Result<String> {
+        self.take_while(|c| c.is_ascii_alphanumeric() || c == '_')
+    }
+
+    /// Literals end at whitespaces and delimiters, but can contain `:` for resolution schemes.
+    fn parse_literal(&mut self) -> Result<String> {
+        self.take_while(|c| !c.is_whitespace() && !",()[]{}\"".contains(c))
+    }
+
+    fn take_while<F>(&mut self, predicate: F) -> Result<String>
+    where
+        F: Fn(char) -> bool,
+    {
+        let raw = self.raw;
+        let rest = &raw[self.position..];
+        let len = rest.find(|c: char| !predicate(c)).unwrap_or(rest.len());
+
+        if len == 0 {
+            return Err(match self.peek() {
+                Some(c) => anyhow::anyhow!("unexpected `{}` at position {}", c, self.position),
+                None => anyhow::anyhow!("unexpected end of value"),
+            });
+        }
+
+        self.position += len;
+        Ok(rest[..len].to_owned())
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "expected `{}` at position {}",
+                expected,
+                self.position
+            ))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let raw = self.raw;
+        let rest = &raw[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.raw[self.position..].chars().next()
+    }
+}
+
+/// Splits comma-separated types without breaking up nested tuples and generic arguments.
+fn split_top_level(raw: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (ind, c) in raw.char_indices() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&raw[start..ind]);
+                start = ind + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&raw[start..]);
+
+    // Single-element tuples have a trailing comma
+    parts
+        .into_iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn find_member<'a>(
+    members: &'a [(String, Value)],
+    name: &str,
+    type_name: &str,
+) -> Result<&'a Value> {
+    members
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+        .ok_or_else(|| anyhow::anyhow!("missing member `{}` of {}", name, type_name))
+}
+
+fn check_unknown_members(
+    members: &[(String, Value)],
+    known: &[&str],
+    type_name: &str,
+) -> Result<()> {
+    match members
+        .iter()
+        .find(|(key, _)| !known.contains(&key.as_str()))
+    {
+        Some((key, _)) => Err(anyhow::anyhow!("unknown member `{}` of {}", key, type_name)),
+        None => Ok(()),
+    }
+}
+
+/// Whether the raw value starts with something that could be a scheme, including token names from
+/// the address book.
+fn has_scheme_prefix(raw: &str) -> bool {
+    raw.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    })
+}
+
+fn single_felt(decoded: Vec<Felt>) -> Result<Felt> {
+    if decoded.len() == 1 {
+        Ok(decoded[0])
+    } else {
+        Err(anyhow::anyhow!(
+            "expected 1 element but found {}",
+            decoded.len()
+        ))
+    }
+}
+
+fn next_felt<'a>(data: &mut std::slice::Iter<'a, Felt>) -> Result<&'a Felt> {
+    data.next()
+        .ok_or_else(|| anyhow::anyhow!("unexpected end of data while decoding"))
+}
+
+fn felt_to_usize(felt: &Felt) -> Result<usize> {
+    felt_to_biguint(felt)
+        .to_usize()
+        .ok_or_else(|| anyhow::anyhow!("value {:#x} out of range for usize", felt))
+}
+
+/// Negative values are represented as their field element negation.
+fn signed_magnitude(value: &Felt) -> (bool, BigUint) {
+    let negated = Felt::ZERO - *value;
+    if felt_to_biguint(value) > felt_to_biguint(&negated) {
+        (true, felt_to_biguint(&negated))
+    } else {
+        (false, felt_to_biguint(value))
+    }
+}
+
+fn signed_in_range(negative: bool, magnitude: &BigUint, bits: u32) -> bool {
+    let limit = BigUint::from(1u32) << (bits - 1);
+    if negative {
+        magnitude <= &limit
+    } else {
+        magnitude < &limit
+    }
+}
+
+fn felt_to_biguint(felt: &Felt) -> BigUint {
+    BigUint::from_bytes_be(&felt.to_bytes_be())
+}
+
+fn biguint_to_felt(value: &BigUint) -> Felt {
+    Felt::from_bytes_be_slice(&value.to_bytes_be())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use starknet::macros::felt;
+
+    use super::*;
+
+    use crate::{address_book::AddressBookResolver, chain_id::OfflineSource};
+
+    const TEST_ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "demo::Pair",
+            "members": [
+                { "name": "id", "type": "core::integer::u8" },
+                { "name": "name", "type": "core::felt252" }
+            ]
+        },
+        {
+            "type": "enum",
+            "name": "core::option::Option::<demo::Pair>",
+            "variants": [
+                { "name": "Some", "type": "demo::Pair" },
+                { "name": "None", "type": "()" }
+            ]
+        },
+        {
+            "type": "function",
+            "name": "demo",
+            "inputs": [
+                { "name": "amount", "type": "core::integer::u256" },
+                { "name": "delta", "type": "core::integer::i8" },
+                { "name": "flag", "type": "core::bool" },
+                { "name": "items", "type": "core::array::Span::<core::integer::u32>" },
+                { "name": "pair", "type": "(core::felt252, core::integer::u16)" },
+                {
+                    "name": "entries",
+                    "type": "core::array::Array::<core::option::Option::<demo::Pair>>"
+                }
+            ],
+            "outputs": [],
+            "state_mutability": "external"
+        },
+        {
+            "type": "function",
+            "name": "set_delta",
+            "inputs": [{ "name": "delta", "type": "core::integer::i8" }],
+            "outputs": [],
+            "state_mutability": "external"
+        }
+    ]"#;
+
+    fn test_abi() -> ContractAbi {
+        ContractAbi {
+            entries: serde_json::from_str(TEST_ABI).unwrap(),
+        }
+    }
+
+    fn offline_decoder() -> FeltDecoder<OfflineSource> {
+        FeltDecoder::new(AddressBookResolver::new(OfflineSource))
+    }
+
+    async fn encode(abi: &ContractAbi, function: &str, args: &[&str]) -> Result<Vec<Felt>> {
+        abi.encode_function_arguments(
+            get_selector_from_name(function).unwrap(),
+            &args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>(),
+            &offline_decoder(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_encode_function_arguments() {
+        let calldata = encode(
+            &test_abi(),
+            "demo",
+            &[
+                "340282366920938463463374607431768211456",
+                "-1",
+                "true",
+                "[1, 2]",
+                "(\"hi\", 0x10)",
+                "[Some({ id: 1, name: \"alice\" }), None]",
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            calldata,
+            vec![
+                // u256 of 2^128
+                Felt::ZERO,
+                Felt::ONE,
+                // i8 of -1
+                Felt::ZERO - Felt::ONE,
+                // bool
+                Felt::ONE,
+                // Span<u32>
+                Felt::TWO,
+                Felt::ONE,
+                Felt::TWO,
+                // (felt252, u16)
+                felt!("0x6869"),
+                felt!("0x10"),
+                // Array<Option<Pair>>
+                Felt::TWO,
+                Felt::ZERO,
+                Felt::ONE,
+                felt!("0x616c696365"),
+                Felt::ONE,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encode_argument_errors() {
+        let abi = test_abi();
+
+        // Wrong argument count
+        assert!(encode(&abi, "set_delta", &[]).await.is_err());
+        // Unknown function
+        assert!(encode(&abi, "unknown", &["1"]).await.is_err());
+        // Missing struct member and unknown enum variant
+        let valid_args = ["1", "1", "true", "[]", "(1, 1)"];
+        for entries in ["[Some({ id: 1 })]", "[Other]", "[Some]"] {
+            let args = valid_args
+                .iter()
+                .copied()
+                .chain([entries])
+                .collect::<Vec<_>>();
+            assert!(encode(&abi, "demo", &args).await.is_err(), "{}", entries);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encode_i8_bounds() {
+        let abi = test_abi();
+
+        for (value, expected) in [
+            ("127", Felt::from(127u64)),
+            ("0x7f", Felt::from(127u64)),
+            ("0", Felt::ZERO),
+            ("-128", Felt::ZERO - Felt::from(128u64)),
+            ("-0x80", Felt::ZERO - Felt::from(128u64)),
+            ("i8:-128", Felt::ZERO - Felt::from(128u64)),
+        ] {
+            assert_eq!(
+                encode(&abi, "set_delta", &[value]).await.unwrap(),
+                vec![expected],
+                "{}",
+                value
+            );
+        }
+
+        for value in ["128", "0x80", "-129", "-200", "-0x81", "u8:200"] {
+            assert!(
+                encode(&abi, "set_delta", &[value]).await.is_err(),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_with_type_hints() {
+        assert_eq!(
+            ContractAbi::decode_with_type_hints(
+                "u256, i8, bool, str",
+                &[
+                    Felt::ZERO,
+                    Felt::ONE,
+                    Felt::ZERO - Felt::from(128u64),
+                    Felt::ONE,
+                    felt!("0x6869"),
+                ],
+            )
+            .unwrap(),
+            json!(["340282366920938463463374607431768211456", -128, true, "hi"])
+        );
+
+        // Single values are not wrapped in arrays
+        assert_eq!(
+            ContractAbi::decode_with_type_hints(
+                "bytearray",
+                &[Felt::ZERO, felt!("0x68656c6c6f"), Felt::from(5u64)],
+            )
+            .unwrap(),
+            json!("hello")
+        );
+        assert_eq!(
+            ContractAbi::decode_with_type_hints(
+                "array<u128>",
+                &[Felt::TWO, Felt::ONE, Felt::from(u128::MAX)],
+            )
+            .unwrap(),
+            json!(["1", "340282366920938463463374607431768211455"])
+        );
+
+        // Trailing and missing elements
+        assert!(ContractAbi::decode_with_type_hints("u8", &[Felt::ONE, Felt::ONE]).is_err());
+        assert!(ContractAbi::decode_with_type_hints("u256", &[Felt::ONE]).is_err());
+        assert!(ContractAbi::decode_with_type_hints("unknown", &[Felt::ONE]).is_err());
+    }
+
+    #[test]
+    fn test_decode_named_types() {
+        let output_type =
+            AbiType::parse("core::array::Array::<core::option::Option::<demo::Pair>>").unwrap();
+
+        assert_eq!(
+            test_abi()
+                .decode_values(
+                    &[output_type],
+                    &[
+                        Felt::TWO,
+                        Felt::ZERO,
+                        Felt::ONE,
+                        felt!("0x616c696365"),
+                        Felt::ONE,
+                    ],
+                )
+                .unwrap(),
+            json!([
+                {
+                    "Some": {
+                        "id": 1,
+                        "name": "0x0000000000000000000000000000000000000000000000000000616c696365"
+                    }
+                },
+                "None"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_i8_bounds() {
+        for (value, expected) in [
+            (Felt::from(127u64), json!(127)),
+            (Felt::ZERO, json!(0)),
+            (Felt::ZERO - Felt::ONE, json!(-1)),
+            (Felt::ZERO - Felt::from(128u64), json!(-128)),
+        ] {
+            assert_eq!(
+                ContractAbi::decode_with_type_hints("i8", &[value]).unwrap(),
+                expected
+            );
+        }
+
+        for value in [Felt::from(128u64), Felt::ZERO - Felt::from(129u64)] {
+            assert!(ContractAbi::decode_with_type_hints("i8", &[value]).is_err());
+        }
+    }
+}
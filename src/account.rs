@@ -1,4 +1,4 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{cell::OnceCell, fmt::Display, path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
@@ -14,11 +14,13 @@ use starknet::{
     },
     macros::{felt, selector},
     providers::Provider,
-    signers::{LocalWallet, SigningKey},
+    signers::{LocalWallet, Signer, SigningKey},
 };
 
 use crate::{
-    signer::{AnySigner, SignerArgs, SignerResolutionTask},
+    config::PROJECT_CONFIG_FILE_NAME,
+    provider::ExtendedProvider,
+    signer::{AnySigner, SignerArgs, SignerResolutionTask, SignerResolutionTaskContent},
     utils::is_affected_braavos_class,
 };
 
@@ -229,11 +231,28 @@ pub struct AccountArgs {
         env = "STARKNET_ACCOUNT",
         help = "Path to account config JSON file"
     )]
-    account: String,
+    account: Option<String>,
     #[clap(flatten)]
     signer: SignerArgs,
 }
 
+/// Resolves the `self:` argument resolution scheme, either lazily from account options as loading
+/// the signer might require user interaction, or from an account that has already been resolved.
+pub struct SelfAccountResolver {
+    source: SelfAccountSource,
+    address: OnceCell<Felt>,
+    public_key: OnceCell<Felt>,
+}
+
+enum SelfAccountSource {
+    Options {
+        account: AccountArgs,
+        provider: Arc<ExtendedProvider>,
+    },
+    /// Both the address and the public key are already known.
+    Resolved,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AccountConfig {
     pub version: u64,
@@ -270,6 +289,13 @@ pub struct BuiltinAccount {
     pub private_key: Felt,
 }
 
+/// Account options resolved into either a built-in account, or an account config file with the
+/// signer for it. Resolving loads the signer, so it should only be done once per command.
+pub enum ResolvedAccount {
+    Builtin(&'static BuiltinAccount),
+    File(AccountConfig, AnySigner),
+}
+
 pub enum AccountVariantType {
     OpenZeppelinLegacy,
     ArgentLegacy,
@@ -374,74 +400,221 @@ pub struct BraavosDeploymentContext {
 impl AccountArgs {
     pub async fn into_account<P>(self, provider: P) -> Result<SingleOwnerAccount<P, AnySigner>>
     where
-        P: Provider + Send + Sync,
+        P: Provider + AsRef<ExtendedProvider> + Send + Sync,
     {
-        let signer = self.signer.into_task()?;
-
-        let mut account = if let Some(builtin_account) = find_builtin_account(&self.account) {
-            if matches!(signer, SignerResolutionTask::Strong(_)) {
-                // The user is supplying a signer explicitly when using a built-in account. This
-                // might be legitimate if the built-in account key has been modified, but it's more
-                // likely a user error. We would simply reject it here. Advanced users can always
-                // fetch the account into a file and use from there anyways.
-                anyhow::bail!(
-                    "do not supply signer options when using a built-in account ({})",
-                    builtin_account.id
-                );
-            }
+        self.resolve(provider.as_ref())
+            .await?
+            .into_account(provider)
+            .await
+    }
 
-            let chain_id = provider.chain_id().await?;
-
-            SingleOwnerAccount::new(
-                provider,
-                AnySigner::LocalWallet(LocalWallet::from_signing_key(
-                    SigningKey::from_secret_scalar(builtin_account.private_key),
-                )),
-                builtin_account.address,
-                chain_id,
-                // All built-in accounts are now on Cairo 1
-                ExecutionEncoding::New,
-            )
-        } else {
-            let signer = signer.resolve().await?;
-            let account = PathBuf::from(shellexpand::tilde(&self.account).into_owned());
+    /// Resolves the account address along with its signer for signing off-chain messages. Unlike
+    /// [`Self::into_account`], the account doesn't have to be deployed.
+    pub async fn into_address_and_signer(
+        self,
+        provider: &ExtendedProvider,
+    ) -> Result<(Felt, AnySigner)> {
+        let account = self.resolve(provider).await?;
+        let address = account.address()?;
+
+        Ok(match account {
+            ResolvedAccount::Builtin(builtin_account) => (address, builtin_account.signer()),
+            ResolvedAccount::File(_, signer) => (address, signer),
+        })
+    }
+
+    pub async fn resolve(self, provider: &ExtendedProvider) -> Result<ResolvedAccount> {
+        let signer = resolve_signer_task(self.signer, provider)?;
+        let account = resolve_account_id(self.account, provider)?;
+
+        match find_builtin_account(&account) {
+            Some(builtin_account) => {
+                if matches!(signer, SignerResolutionTask::Strong(_)) {
+                    // The user is supplying a signer explicitly when using a built-in account.
+                    // This might be legitimate if the built-in account key has been modified, but
+                    // it's more likely a user error. We would simply reject it here. Advanced users
+                    // can always fetch the account into a file and use from there anyways.
+                    anyhow::bail!(
+                        "do not supply signer options when using a built-in account ({})",
+                        builtin_account.id
+                    );
+                }
 
-            if !account.exists() {
-                anyhow::bail!("account config file not found");
+                Ok(ResolvedAccount::Builtin(builtin_account))
             }
+            None => {
+                let signer = signer.resolve().await?;
+                let account_config = load_account_config(&account)?;
 
-            let account_config: AccountConfig =
-                serde_json::from_reader(&mut std::fs::File::open(&account)?)?;
+                Ok(ResolvedAccount::File(account_config, signer))
+            }
+        }
+    }
+}
 
-            if is_affected_braavos_class(account_config.deployment.class_hash()) {
-                eprintln!(
-                    "{}",
-                    "WARNING: This Braavos account contract does not work with JSON-RPC v0.8.x. \
-                    Transactions WILL fail."
-                        .bright_magenta()
-                );
+impl ResolvedAccount {
+    pub async fn into_account<P>(self, provider: P) -> Result<SingleOwnerAccount<P, AnySigner>>
+    where
+        P: Provider + Send + Sync,
+    {
+        let mut account = match self {
+            ResolvedAccount::Builtin(builtin_account) => {
+                let chain_id = provider.chain_id().await?;
+
+                SingleOwnerAccount::new(
+                    provider,
+                    builtin_account.signer(),
+                    builtin_account.address,
+                    chain_id,
+                    // All built-in accounts are now on Cairo 1
+                    ExecutionEncoding::New,
+                )
             }
+            ResolvedAccount::File(account_config, signer) => {
+                if is_affected_braavos_class(account_config.deployment.class_hash()) {
+                    eprintln!(
+                        "{}",
+                        "WARNING: This Braavos account contract does not work with JSON-RPC \
+                        v0.8.x. Transactions WILL fail."
+                            .bright_magenta()
+                    );
+                }
 
-            let account_address = match account_config.deployment {
-                DeploymentStatus::Undeployed(_) => anyhow::bail!("account not deployed"),
-                DeploymentStatus::Deployed(inner) => inner.address,
-            };
+                let account_address = match account_config.deployment {
+                    DeploymentStatus::Undeployed(_) => anyhow::bail!("account not deployed"),
+                    DeploymentStatus::Deployed(inner) => inner.address,
+                };
 
-            let chain_id = provider.chain_id().await?;
+                let chain_id = provider.chain_id().await?;
 
-            SingleOwnerAccount::new(
-                provider,
-                signer,
-                account_address,
-                chain_id,
-                account_config.variant.execution_encoding(),
-            )
+                SingleOwnerAccount::new(
+                    provider,
+                    signer,
+                    account_address,
+                    chain_id,
+                    account_config.variant.execution_encoding(),
+                )
+            }
         };
 
         account.set_block_id(BlockId::Tag(BlockTag::Pending));
 
         Ok(account)
     }
+
+    /// The account address. Undeployed accounts resolve to the addresses they would be deployed at.
+    pub fn address(&self) -> Result<Felt> {
+        Ok(match self {
+            ResolvedAccount::Builtin(builtin_account) => builtin_account.address,
+            ResolvedAccount::File(account_config, _) => match &account_config.deployment {
+                DeploymentStatus::Undeployed(_) => account_config.deploy_account_address()?,
+                DeploymentStatus::Deployed(inner) => inner.address,
+            },
+        })
+    }
+
+    pub async fn public_key(&self) -> Result<Felt> {
+        Ok(match self {
+            ResolvedAccount::Builtin(builtin_account) => {
+                SigningKey::from_secret_scalar(builtin_account.private_key)
+                    .verifying_key()
+                    .scalar()
+            }
+            ResolvedAccount::File(_, signer) => signer.get_public_key().await?.scalar(),
+        })
+    }
+}
+
+impl SelfAccountResolver {
+    pub fn new(account: AccountArgs, provider: Arc<ExtendedProvider>) -> Self {
+        Self {
+            source: SelfAccountSource::Options { account, provider },
+            address: OnceCell::new(),
+            public_key: OnceCell::new(),
+        }
+    }
+
+    /// Uses an account that has already been resolved, so that its signer isn't loaded again.
+    pub async fn from_resolved(account: &ResolvedAccount) -> Result<Self> {
+        Ok(Self {
+            source: SelfAccountSource::Resolved,
+            address: OnceCell::from(account.address()?),
+            public_key: OnceCell::from(account.public_key().await?),
+        })
+    }
+
+    /// Resolves the account address without loading the signer. Undeployed accounts resolve to the
+    /// addresses they would be deployed at.
+    pub fn address(&self) -> Result<Felt> {
+        if let Some(address) = self.address.get() {
+            return Ok(*address);
+        }
+
+        let (account, provider) = self.options();
+        let account = resolve_account_id(account.account.clone(), provider)?;
+        let address = match find_builtin_account(&account) {
+            Some(builtin_account) => builtin_account.address,
+            None => {
+                let account_config = load_account_config(&account)?;
+                match &account_config.deployment {
+                    DeploymentStatus::Undeployed(_) => account_config.deploy_account_address()?,
+                    DeploymentStatus::Deployed(inner) => inner.address,
+                }
+            }
+        };
+
+        // It's OK if another thread set it first
+        let _ = self.address.set(address);
+
+        Ok(address)
+    }
+
+    /// Resolves the public key of the signer, or the built-in account's if one is used.
+    pub async fn public_key(&self) -> Result<Felt> {
+        if let Some(public_key) = self.public_key.get() {
+            return Ok(*public_key);
+        }
+
+        let (account, provider) = self.options();
+
+        // A missing account is fine here as only the signer is needed
+        let builtin_account = resolve_account_id(account.account.clone(), provider)
+            .ok()
+            .and_then(|account| find_builtin_account(&account));
+
+        let public_key = match builtin_account {
+            Some(builtin_account) => SigningKey::from_secret_scalar(builtin_account.private_key)
+                .verifying_key()
+                .scalar(),
+            None => {
+                let signer = resolve_signer_task(account.signer.clone(), provider)?
+                    .resolve()
+                    .await?;
+                signer.get_public_key().await?.scalar()
+            }
+        };
+
+        // It's OK if another thread set it first
+        let _ = self.public_key.set(public_key);
+
+        Ok(public_key)
+    }
+
+    fn options(&self) -> (&AccountArgs, &ExtendedProvider) {
+        match &self.source {
+            SelfAccountSource::Options { account, provider } => (account, provider.as_ref()),
+            // Resolved accounts always come with both values cached
+            SelfAccountSource::Resolved => unreachable!("self account already resolved"),
+        }
+    }
+}
+
+impl BuiltinAccount {
+    fn signer(&self) -> AnySigner {
+        AnySigner::LocalWallet(LocalWallet::from_signing_key(
+            SigningKey::from_secret_scalar(self.private_key),
+        ))
+    }
 }
 
 impl AccountConfig {
@@ -590,6 +763,21 @@ impl BraavosSigner {
     }
 }
 
+impl AccountVariantType {
+    /// Encoding of `__execute__` calldata for accounts of this type. All Cairo 0 accounts use the
+    /// legacy encoding.
+    pub fn default_execution_encoding(&self) -> ExecutionEncoding {
+        match self {
+            AccountVariantType::OpenZeppelinLegacy
+            | AccountVariantType::ArgentLegacy
+            | AccountVariantType::BraavosLegacy => ExecutionEncoding::Legacy,
+            AccountVariantType::Argent
+            | AccountVariantType::Braavos
+            | AccountVariantType::OpenZeppelin => ExecutionEncoding::New,
+        }
+    }
+}
+
 impl Display for AccountVariantType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -603,7 +791,53 @@ impl Display for AccountVariantType {
     }
 }
 
-fn find_builtin_account(id: &str) -> Option<&'static BuiltinAccount> {
+/// The network's default signer is used only when no signer option is supplied at all, and is
+/// never considered an explicit choice.
+fn resolve_signer_task(
+    signer: SignerArgs,
+    provider: &ExtendedProvider,
+) -> Result<SignerResolutionTask> {
+    Ok(match signer.into_task(provider.project_config())? {
+        SignerResolutionTask::None => match provider.network_signer() {
+            Some(network_signer) => SignerResolutionTask::Weak(
+                SignerResolutionTaskContent::from_network_signer(network_signer)?,
+            ),
+            None => SignerResolutionTask::None,
+        },
+        task => task,
+    })
+}
+
+fn resolve_account_id(account: Option<String>, provider: &ExtendedProvider) -> Result<String> {
+    match account {
+        Some(account) => Ok(account),
+        None => provider
+            .project_config()
+            .and_then(|config| config.resolved_account())
+            .or_else(|| provider.network_account().map(|account| account.to_owned()))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "no account specified. Use --account (STARKNET_ACCOUNT), set `account` in {}, \
+                    or set a default account for the network",
+                    PROJECT_CONFIG_FILE_NAME
+                )
+            }),
+    }
+}
+
+fn load_account_config(account: &str) -> Result<AccountConfig> {
+    let account = PathBuf::from(shellexpand::tilde(account).into_owned());
+
+    if !account.exists() {
+        anyhow::bail!("account config file not found");
+    }
+
+    Ok(serde_json::from_reader(&mut std::fs::File::open(
+        &account,
+    )?)?)
+}
+
+pub fn find_builtin_account(id: &str) -> Option<&'static BuiltinAccount> {
     BUILTIN_ACCOUNTS
         .iter()
         .find(|&account| account.id == id || account.aliases.contains(&id))
@@ -612,3 +846,82 @@ fn find_builtin_account(id: &str) -> Option<&'static BuiltinAccount> {
 fn true_as_default() -> bool {
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{address_book::AddressBookResolver, chain_id::OfflineSource, decode::FeltDecoder};
+
+    /// The public key of private key `1`, which is the x-coordinate of the curve generator.
+    const GENERATOR_X: Felt =
+        felt!("0x01ef15c18599971b7beced415a40f0c7deacfd9b0d1819e03d723d8bc943cfca");
+
+    fn file_account() -> ResolvedAccount {
+        let account_config = serde_json::from_str(
+            r#"{
+                "version": 1,
+                "variant": {
+                    "type": "open_zeppelin",
+                    "version": 1,
+                    "public_key": "0x1",
+                    "legacy": false
+                },
+                "deployment": {
+                    "status": "deployed",
+                    "class_hash": "0x1",
+                    "address": "0x1234"
+                }
+            }"#,
+        )
+        .unwrap();
+        let signer = AnySigner::LocalWallet(LocalWallet::from_signing_key(
+            SigningKey::from_secret_scalar(Felt::ONE),
+        ));
+
+        ResolvedAccount::File(account_config, signer)
+    }
+
+    #[tokio::test]
+    async fn test_self_scheme_with_resolved_account() {
+        let decoder = FeltDecoder::new(AddressBookResolver::new(OfflineSource)).with_self_account(
+            SelfAccountResolver::from_resolved(&file_account())
+                .await
+                .unwrap(),
+        );
+
+        assert_eq!(
+            decoder.decode_single("self:address").await.unwrap(),
+            felt!("0x1234")
+        );
+        assert_eq!(
+            decoder.decode_single("self:pubkey").await.unwrap(),
+            GENERATOR_X
+        );
+        assert!(decoder.decode_single("self:other").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_self_scheme_with_builtin_account() {
+        let builtin_account = find_builtin_account("katana-0").unwrap();
+        let resolver =
+            SelfAccountResolver::from_resolved(&ResolvedAccount::Builtin(builtin_account))
+                .await
+                .unwrap();
+
+        assert_eq!(resolver.address().unwrap(), builtin_account.address);
+        assert_eq!(
+            resolver.public_key().await.unwrap(),
+            SigningKey::from_secret_scalar(builtin_account.private_key)
+                .verifying_key()
+                .scalar()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_self_scheme_without_account() {
+        let decoder = FeltDecoder::new(AddressBookResolver::new(OfflineSource));
+
+        assert!(decoder.decode_single("self:address").await.is_err());
+    }
+}
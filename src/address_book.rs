@@ -1,62 +1,100 @@
-// Very temporary implementation of a hard-coded address book
-
-use std::cell::OnceCell;
+use std::{
+    cell::OnceCell,
+    collections::HashMap,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
 
 use anyhow::Result;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use starknet::{
-    core::{chain_id, types::Felt},
+    core::{
+        chain_id,
+        types::Felt,
+        utils::{cairo_short_string_to_felt, parse_cairo_short_string},
+    },
     macros::{felt, short_string},
 };
 
-use crate::chain_id::ChainIdSource;
+use crate::{
+    account::BUILTIN_ACCOUNTS,
+    chain_id::ChainIdSource,
+    config::{ProjectConfig, ProjectConfigSource},
+    decode::is_scheme_name,
+    profile::Profiles,
+    starknet_id::{address_to_domain, domain_to_address, ContractCaller, NAMING_CONTRACT_ENTRY},
+};
 
 const CHAIN_ID_KATANA: Felt = felt!("0x4b4154414e41");
 
-pub const HARDCODED_ADDRESS_BOOK: [AddressBookEntry; 9] = [
+/// Built-in entries, which have the lowest priority and can be shadowed by user-defined ones.
+pub const HARDCODED_ADDRESS_BOOK: [AddressBookEntry; 11] = [
     AddressBookEntry {
         chain_id: chain_id::MAINNET,
         name: "eth",
         address: felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: chain_id::MAINNET,
         name: "strk",
         address: felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: chain_id::MAINNET,
         name: "usdc",
         address: felt!("0x053c91253bc9682c04929ca02ed00b3e423f6710d2ee7e0d5ebb06f3ecf368a8"),
+        decimals: Some(6),
     },
     AddressBookEntry {
         chain_id: short_string!("SN_SEPOLIA"),
         name: "eth",
         address: felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: short_string!("SN_SEPOLIA"),
         name: "strk",
         address: felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: short_string!("SN_INTEGRATION_SEPOLIA"),
         name: "eth",
         address: felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: short_string!("SN_INTEGRATION_SEPOLIA"),
         name: "strk",
         address: felt!("0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: CHAIN_ID_KATANA,
         name: "eth",
         address: felt!("0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+        decimals: Some(18),
     },
     AddressBookEntry {
         chain_id: chain_id::MAINNET,
         name: "zklend",
         address: felt!("0x04c0a5193d58f74fbace4b74dcf65481e734ed1714121bdc571da345540efa05"),
+        decimals: None,
+    },
+    AddressBookEntry {
+        chain_id: chain_id::MAINNET,
+        name: NAMING_CONTRACT_ENTRY,
+        address: felt!("0x06ac597f8116f886fa1c97a23fa4e08299975ecaf6b598873ca6792b9bbfb678"),
+        decimals: None,
+    },
+    AddressBookEntry {
+        chain_id: short_string!("SN_SEPOLIA"),
+        name: NAMING_CONTRACT_ENTRY,
+        address: felt!("0x0154bc2e1af9260b9e66af0e9c46fc757ff893b3ff6a85718a810baf1474"),
+        decimals: None,
     },
 ];
 
@@ -64,19 +102,157 @@ pub struct AddressBookEntry {
     pub chain_id: Felt,
     pub name: &'static str,
     pub address: Felt,
+    /// Decimals of token entries, which enable amount schemes named after the entries.
+    pub decimals: Option<u8>,
+}
+
+/// User-defined address book entries, keyed by chain ID in Cairo short string representation and
+/// then by name.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AddressBook {
+    pub chains: IndexMap<String, IndexMap<String, NamedAddress>>,
+}
+
+/// A user-defined entry, written either as a bare address, or as a table with the token decimals
+/// in addition to the address, which enables the amount scheme named after the entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum NamedAddress {
+    Address(Felt),
+    Token { address: Felt, decimals: u8 },
 }
 
 /// A resolver that lazily fetches chain id to avoid unnecessary network calls.
 pub struct AddressBookResolver<S> {
     chain_id_source: S,
     chain_id: OnceCell<Felt>,
+    /// The user address book file, which is only read once when first needed.
+    user_address_book: OnceCell<AddressBook>,
+    /// Entries from the project file, which is loaded along with the provider.
+    project_address_book: Option<AddressBook>,
+}
+
+impl AddressBook {
+    /// Loads the user address book file, which is empty if it doesn't exist yet.
+    pub fn load() -> Result<Self> {
+        let path = Self::get_address_book_path()?;
+
+        if path.exists() {
+            Self::load_from(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Loads and validates an address book from any file, such as one to be imported.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let mut file = std::fs::File::open(path)?;
+        let mut buffer = String::new();
+        file.read_to_string(&mut buffer)?;
+
+        let address_book: Self = toml::from_str(&buffer)
+            .map_err(|err| anyhow::anyhow!("invalid address book {}: {}", path.display(), err))?;
+        address_book
+            .validate()
+            .map_err(|err| anyhow::anyhow!("invalid address book {}: {}", path.display(), err))?;
+
+        Ok(address_book)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let serialized = toml::to_string_pretty(self)?;
+
+        let config_folder = Profiles::get_config_folder()?;
+        if !config_folder.exists() {
+            std::fs::create_dir_all(config_folder)?;
+        }
+
+        let path = Self::get_address_book_path()?;
+        let mut file = std::fs::File::create(path)?;
+
+        file.write_all(serialized.as_bytes())?;
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (chain_id, entries) in self.chains.iter() {
+            if cairo_short_string_to_felt(chain_id).is_err() {
+                anyhow::bail!("invalid chain ID \"{}\"", chain_id);
+            }
+
+            for (name, entry) in entries.iter() {
+                validate_name(name)?;
+                if entry.decimals().is_some() {
+                    validate_token_name(name)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, chain_id: Felt, name: &str) -> Option<NamedAddress> {
+        self.chains
+            .iter()
+            .filter(|(key, _)| cairo_short_string_to_felt(key).ok() == Some(chain_id))
+            .find_map(|(_, entries)| entries.get(name).copied())
+    }
+
+    /// Whether any chain has a token entry with the name.
+    fn has_token(&self, name: &str) -> bool {
+        self.chains.values().any(|entries| {
+            entries
+                .get(name)
+                .is_some_and(|entry| entry.decimals().is_some())
+        })
+    }
+
+    /// Gets the entries of a chain for modification, creating an empty set if necessary.
+    pub fn chain_mut(&mut self, chain_id: Felt) -> Result<&mut IndexMap<String, NamedAddress>> {
+        let key = parse_cairo_short_string(&chain_id)
+            .map_err(|_| anyhow::anyhow!("chain ID {:#x} is not a valid short string", chain_id))?;
+
+        Ok(self.chains.entry(key).or_default())
+    }
+
+    fn get_address_book_path() -> Result<PathBuf> {
+        let mut path = Profiles::get_config_folder()?;
+        path.push("address_book.toml");
+        Ok(path)
+    }
+}
+
+impl NamedAddress {
+    pub fn address(&self) -> Felt {
+        match self {
+            Self::Address(address) | Self::Token { address, .. } => *address,
+        }
+    }
+
+    pub fn decimals(&self) -> Option<u8> {
+        match self {
+            Self::Address(_) => None,
+            Self::Token { decimals, .. } => Some(*decimals),
+        }
+    }
 }
 
-impl<S> AddressBookResolver<S> {
+impl<S> AddressBookResolver<S>
+where
+    S: ProjectConfigSource,
+{
     pub fn new(chain_id_source: S) -> Self {
+        let project_address_book = chain_id_source
+            .project_config()
+            .map(|config| config.address_book.clone());
+
         Self {
             chain_id_source,
             chain_id: OnceCell::new(),
+            user_address_book: OnceCell::new(),
+            project_address_book,
         }
     }
 }
@@ -86,9 +262,80 @@ where
     S: ChainIdSource,
 {
     pub async fn resolve_name(&self, name: &str) -> Result<Option<Felt>> {
+        Ok(self.resolve_entry(name).await?.map(|entry| entry.address()))
+    }
+
+    /// Looks up the decimals of a token entry on the current chain. The chain ID is only fetched
+    /// when the name is a token on any chain, so that other schemes never need network access.
+    pub async fn resolve_token_decimals(&self, name: &str) -> Result<Option<u8>> {
+        let is_token = self.user_address_book()?.has_token(name)
+            || self
+                .project_address_book
+                .as_ref()
+                .is_some_and(|address_book| address_book.has_token(name))
+            || HARDCODED_ADDRESS_BOOK
+                .iter()
+                .any(|entry| entry.name == name && entry.decimals.is_some());
+        if !is_token {
+            return Ok(None);
+        }
+
+        Ok(self
+            .resolve_entry(name)
+            .await?
+            .and_then(|entry| entry.decimals()))
+    }
+
+    /// Finds the entry that takes precedence for the name on the current chain.
+    async fn resolve_entry(&self, name: &str) -> Result<Option<NamedAddress>> {
+        let chain_id = self.chain_id().await?;
+
+        // User file entries come first, then project-local entries, and finally built-in ones
+        if let Some(entry) = self.user_address_book()?.get(chain_id, name) {
+            return Ok(Some(entry));
+        }
+
+        if let Some(entry) = self
+            .project_address_book
+            .as_ref()
+            .and_then(|address_book| address_book.get(chain_id, name))
+        {
+            return Ok(Some(entry));
+        }
+
+        Ok(HARDCODED_ADDRESS_BOOK.iter().find_map(|entry| {
+            if entry.chain_id == chain_id && entry.name == name {
+                Some(match entry.decimals {
+                    Some(decimals) => NamedAddress::Token {
+                        address: entry.address,
+                        decimals,
+                    },
+                    None => NamedAddress::Address(entry.address),
+                })
+            } else {
+                None
+            }
+        }))
+    }
+
+    fn user_address_book(&self) -> Result<&AddressBook> {
+        if let Some(address_book) = self.user_address_book.get() {
+            return Ok(address_book);
+        }
+
+        let address_book = AddressBook::load()?;
+
+        // It's OK if another thread set it first
+        let _ = self.user_address_book.set(address_book);
+
+        // Always set by now
+        Ok(self.user_address_book.get().unwrap())
+    }
+
+    async fn chain_id(&self) -> Result<Felt> {
         let chain_id_cell = &self.chain_id;
 
-        let chain_id = match chain_id_cell.get() {
+        Ok(match chain_id_cell.get() {
             Some(chain_id) => *chain_id,
             None => {
                 let chain_id = self.chain_id_source.get_chain_id().await?;
@@ -98,14 +345,109 @@ where
 
                 chain_id
             }
-        };
+        })
+    }
+}
 
-        Ok(HARDCODED_ADDRESS_BOOK.iter().find_map(|entry| {
-            if entry.chain_id == chain_id && entry.name == name {
-                Some(entry.address)
-            } else {
-                None
+impl<S> AddressBookResolver<S>
+where
+    S: ChainIdSource + ContractCaller,
+{
+    /// Resolves a Starknet ID `.stark` domain with the naming contract of the current chain.
+    pub async fn resolve_stark_name(&self, domain: &str) -> Result<Felt> {
+        let naming_contract = self.naming_contract().await?;
+        domain_to_address(&self.chain_id_source, naming_contract, domain).await
+    }
+
+    /// Looks up the main Starknet ID `.stark` domain of an address, if any.
+    pub async fn lookup_stark_name(&self, address: Felt) -> Result<Option<String>> {
+        let naming_contract = self.naming_contract().await?;
+        address_to_domain(&self.chain_id_source, naming_contract, address).await
+    }
+
+    async fn naming_contract(&self) -> Result<Felt> {
+        self.resolve_name(NAMING_CONTRACT_ENTRY)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Starknet ID naming contract unknown for the current chain. Add it to the \
+                    address book as `{}`",
+                    NAMING_CONTRACT_ENTRY
+                )
+            })
+    }
+}
+
+/// Builds a reverse lookup from addresses to names on a chain, for annotating output. When an
+/// address has multiple names, the one that takes precedence in name resolution is used. Built-in
+/// accounts are included by their IDs as they're not specific to any chain.
+pub fn address_labels(
+    chain_id: Felt,
+    project_config: Option<&ProjectConfig>,
+) -> Result<HashMap<Felt, String>> {
+    let mut labels = HashMap::new();
+
+    let user_address_book = AddressBook::load()?;
+    let project_address_book = project_config.map(|config| &config.address_book);
+    for address_book in std::iter::once(&user_address_book).chain(project_address_book) {
+        for (key, entries) in address_book.chains.iter() {
+            if cairo_short_string_to_felt(key).ok() != Some(chain_id) {
+                continue;
             }
-        }))
+
+            for (name, entry) in entries.iter() {
+                labels
+                    .entry(entry.address())
+                    .or_insert_with(|| name.to_owned());
+            }
+        }
     }
+
+    for entry in HARDCODED_ADDRESS_BOOK.iter() {
+        if entry.chain_id == chain_id {
+            labels
+                .entry(entry.address)
+                .or_insert_with(|| entry.name.to_owned());
+        }
+    }
+
+    for account in BUILTIN_ACCOUNTS.iter() {
+        labels
+            .entry(account.address)
+            .or_insert_with(|| account.id.to_owned());
+    }
+
+    Ok(labels)
+}
+
+/// Names are used in the `addr:` scheme, so we keep them simple.
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        anyhow::bail!("address book name must not be empty");
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        anyhow::bail!(
+            "invalid address book name \"{}\": only ASCII letters, digits, `-`, `_` and `.` \
+            are allowed",
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// Token entries enable the amount scheme named after them, which can't shadow built-in schemes.
+pub fn validate_token_name(name: &str) -> Result<()> {
+    if is_scheme_name(name) {
+        anyhow::bail!(
+            "invalid token name \"{}\": the name is reserved for a built-in scheme",
+            name
+        );
+    }
+
+    Ok(())
 }
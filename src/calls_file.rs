@@ -0,0 +1,95 @@
+use std::{io::Read, path::Path};
+
+use anyhow::Result;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+
+/// Multicall file for `--calls-file`. Values are resolved the same way as positional arguments.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallsFile {
+    pub calls: Vec<RawCall>,
+}
+
+/// A contract call before argument resolution.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawCall {
+    pub to: String,
+    pub selector: String,
+    #[serde(default, deserialize_with = "deserialize_values")]
+    pub calldata: Vec<String>,
+}
+
+/// Calldata file for `--calldata-file`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CalldataFile {
+    #[serde(deserialize_with = "deserialize_values")]
+    pub calldata: Vec<String>,
+}
+
+/// Integers are accepted as is, as they're often written unquoted.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FileValue {
+    String(String),
+    Integer(u64),
+}
+
+impl CallsFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        load_file(path)
+    }
+}
+
+impl CalldataFile {
+    pub fn load(path: &Path) -> Result<Self> {
+        load_file(path)
+    }
+}
+
+/// Loads a JSON or TOML file, or reads from stdin if the path is `-`. The format is determined by
+/// the file extension when available.
+fn load_file<T>(path: &Path) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let (content, display_name) = if path == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        (content, "stdin".to_owned())
+    } else {
+        (std::fs::read_to_string(path)?, path.display().to_string())
+    };
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&content)
+            .map_err(|err| anyhow::anyhow!("invalid JSON file {}: {}", display_name, err)),
+        Some("toml") => toml::from_str(&content)
+            .map_err(|err| anyhow::anyhow!("invalid TOML file {}: {}", display_name, err)),
+        _ => match serde_json::from_str(&content) {
+            Ok(value) => Ok(value),
+            Err(json_err) => toml::from_str(&content).map_err(|toml_err| {
+                anyhow::anyhow!(
+                    "unable to parse {} as JSON ({}) or TOML ({})",
+                    display_name,
+                    json_err,
+                    toml_err
+                )
+            }),
+        },
+    }
+}
+
+fn deserialize_values<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Vec::<FileValue>::deserialize(deserializer)?
+        .into_iter()
+        .map(|value| match value {
+            FileValue::String(value) => value,
+            FileValue::Integer(value) => value.to_string(),
+        })
+        .collect())
+}
@@ -20,3 +20,18 @@ where
             .map_err(|err| anyhow::anyhow!("unable to get chain id: {err}"))
     }
 }
+
+/// A stand-in source for local commands, which never touch the network. Schemes that need chain
+/// data fail with an explanatory error instead.
+pub struct OfflineSource;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ChainIdSource for OfflineSource {
+    async fn get_chain_id(&self) -> Result<Felt> {
+        Err(anyhow::anyhow!(
+            "network access is not available in local commands, so address book names, token \
+            amounts, and Starknet ID domains cannot be resolved"
+        ))
+    }
+}
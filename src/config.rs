@@ -0,0 +1,268 @@
+use std::{
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::{de::Visitor, Deserialize};
+
+use crate::{
+    account::find_builtin_account,
+    address_book::AddressBook,
+    chain_id::OfflineSource,
+    network::Network as BuiltinNetwork,
+    profile::{Network, NetworkProvider},
+};
+
+pub(crate) const PROJECT_CONFIG_FILE_NAME: &str = "starkli.toml";
+
+pub(crate) const DEFAULT_POLL_INTERVAL: u64 = 5000;
+
+static PROJECT_CONFIG: OnceLock<Option<ProjectConfig>> = OnceLock::new();
+
+/// Project-local settings from a `starkli.toml` file. Values here take precedence over the user
+/// profiles, but are overridden by command line options and environment variables.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Path to the `starkli.toml` file this config is loaded from.
+    #[serde(skip)]
+    pub path: PathBuf,
+    pub network: Option<String>,
+    /// Account config file path relative to the project file, or a built-in account ID.
+    pub account: Option<String>,
+    /// Keystore file path relative to the project file.
+    pub keystore: Option<String>,
+    pub poll_interval: Option<u64>,
+    #[serde(default)]
+    pub fee: ProjectFeeSettings,
+    /// Networks only available within the project. Their identifiers must not be used by any
+    /// well-known network or any network in the user profiles.
+    #[serde(default)]
+    pub networks: IndexMap<String, Network>,
+    /// Address book entries consulted after the user address book file.
+    #[serde(default)]
+    pub address_book: AddressBook,
+}
+
+/// A source of the project config loaded for the current command, so that it's discovered and
+/// parsed only once.
+pub trait ProjectConfigSource {
+    fn project_config(&self) -> Option<&ProjectConfig>;
+}
+
+/// Fee settings, which accept the same values as their command line option counterparts.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectFeeSettings {
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l1_gas: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l1_gas_price: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l1_gas_price_raw: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l2_gas: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l2_gas_price: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l2_gas_price_raw: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l1_data_gas: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l1_data_gas_price: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_option_value")]
+    pub l1_data_gas_price_raw: Option<String>,
+}
+
+/// Accepts strings and numbers alike, as numeric options are often written unquoted in TOML.
+struct OptionValueVisitor;
+
+impl ProjectConfig {
+    /// Gets the closest `starkli.toml` from the current directory upwards. The file is only
+    /// discovered and parsed once for the whole process.
+    pub fn load() -> Result<Option<&'static Self>> {
+        if let Some(config) = PROJECT_CONFIG.get() {
+            return Ok(config.as_ref());
+        }
+
+        let config = Self::discover()?;
+
+        // It's OK if another thread set it first
+        let _ = PROJECT_CONFIG.set(config);
+
+        // Always set by now
+        Ok(PROJECT_CONFIG.get().unwrap().as_ref())
+    }
+
+    fn discover() -> Result<Option<Self>> {
+        // No project can be discovered without a working directory
+        let current_dir = match std::env::current_dir() {
+            Ok(dir) => dir,
+            Err(_) => return Ok(None),
+        };
+
+        for dir in current_dir.ancestors() {
+            let path = dir.join(PROJECT_CONFIG_FILE_NAME);
+            if path.is_file() {
+                return Self::load_from(path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn load_from(path: PathBuf) -> Result<Self> {
+        let content = std::fs::read_to_string(&path)?;
+
+        let mut config: Self = toml::from_str(&content)
+            .map_err(|err| anyhow::anyhow!("invalid project file {}: {}", path.display(), err))?;
+        config.address_book.validate().map_err(|err| {
+            anyhow::anyhow!(
+                "invalid address book in project file {}: {}",
+                path.display(),
+                err
+            )
+        })?;
+
+        // Project files often come with repositories from untrusted sources. Expanding environment
+        // variables in their endpoints would allow sending secrets to arbitrary servers, while
+        // redefining well-known networks would allow sending transactions to arbitrary servers.
+        for (id, network) in config.networks.iter() {
+            if BuiltinNetwork::from_str(id).is_ok() {
+                anyhow::bail!(
+                    "invalid network `{}` in project file {}: well-known networks cannot be \
+                    redefined in project files",
+                    id,
+                    path.display()
+                );
+            }
+
+            if let NetworkProvider::Rpc(rpc) = &network.provider {
+                if rpc
+                    .endpoints
+                    .iter()
+                    .any(|endpoint| endpoint.references_env_vars())
+                {
+                    anyhow::bail!(
+                        "invalid network `{}` in project file {}: environment variable references \
+                        are only allowed in profiles",
+                        id,
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        config.path = path;
+
+        Ok(config)
+    }
+
+    /// The account with relative paths resolved against the project directory. Built-in account
+    /// IDs are returned as is.
+    pub fn resolved_account(&self) -> Option<String> {
+        self.account.as_ref().map(|account| {
+            if find_builtin_account(account).is_some() {
+                account.to_owned()
+            } else {
+                self.resolve_path(account)
+            }
+        })
+    }
+
+    /// The keystore with relative paths resolved against the project directory.
+    pub fn resolved_keystore(&self) -> Option<String> {
+        self.keystore
+            .as_ref()
+            .map(|keystore| self.resolve_path(keystore))
+    }
+
+    fn resolve_path(&self, path: &str) -> String {
+        let path = PathBuf::from(shellexpand::tilde(path).into_owned());
+
+        let path = match self.path.parent() {
+            Some(project_dir) if path.is_relative() => project_dir.join(path),
+            _ => path,
+        };
+
+        path.to_string_lossy().into_owned()
+    }
+}
+
+impl<T> ProjectConfigSource for Arc<T>
+where
+    T: ProjectConfigSource,
+{
+    fn project_config(&self) -> Option<&ProjectConfig> {
+        self.as_ref().project_config()
+    }
+}
+
+impl ProjectConfigSource for OfflineSource {
+    fn project_config(&self) -> Option<&ProjectConfig> {
+        None
+    }
+}
+
+/// Resolves the transaction poll interval for options that are not supplied from the command line
+/// or environment variables, falling back to the project file and then the built-in default.
+pub fn resolve_poll_interval(
+    poll_interval: Option<u64>,
+    project_config: Option<&ProjectConfig>,
+) -> Duration {
+    let poll_interval = match poll_interval {
+        Some(poll_interval) => poll_interval,
+        None => project_config
+            .and_then(|config| config.poll_interval)
+            .unwrap_or(DEFAULT_POLL_INTERVAL),
+    };
+
+    Duration::from_millis(poll_interval)
+}
+
+impl Visitor<'_> for OptionValueVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "string or number")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.to_owned())
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.to_string())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.to_string())
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.to_string())
+    }
+}
+
+fn deserialize_option_value<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_any(OptionValueVisitor).map(Some)
+}
@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use anyhow::Result;
+use bigdecimal::BigDecimal;
 use num_bigint::BigUint;
 use starknet::{
     core::{
@@ -9,10 +12,24 @@ use starknet::{
     macros::felt,
 };
 
-use crate::{address_book::AddressBookResolver, chain_id::ChainIdSource};
+use crate::{
+    account::SelfAccountResolver,
+    address_book::{validate_name, AddressBookResolver},
+    chain_id::ChainIdSource,
+    hash::HashFunction,
+    starknet_id::ContractCaller,
+    utils::bigdecimal_to_felt,
+};
 
 pub struct FeltDecoder<S> {
     address_book_resolver: AddressBookResolver<S>,
+    self_account_resolver: Option<SelfAccountResolver>,
+}
+
+struct StorageExpression<'a> {
+    name: &'a str,
+    keys: Vec<&'a str>,
+    offset: u64,
 }
 
 #[derive(Clone, Copy)]
@@ -27,14 +44,36 @@ impl<S> FeltDecoder<S> {
     pub fn new(address_book_resolver: AddressBookResolver<S>) -> Self {
         Self {
             address_book_resolver,
+            self_account_resolver: None,
+        }
+    }
+
+    /// Enables the `self:` scheme for commands that take account options.
+    pub fn with_self_account(self, self_account_resolver: SelfAccountResolver) -> Self {
+        Self {
+            self_account_resolver: Some(self_account_resolver),
+            ..self
         }
     }
 }
 
 impl<S> FeltDecoder<S>
 where
-    S: ChainIdSource,
+    S: ChainIdSource + ContractCaller,
 {
+    pub async fn decode_single(&self, raw: &str) -> Result<Felt> {
+        let decoded = self.decode_inner(raw, FallbackOption::None).await?;
+
+        if decoded.len() == 1 {
+            Ok(decoded[0])
+        } else {
+            Err(anyhow::anyhow!(
+                "expected 1 element but found {}",
+                decoded.len()
+            ))
+        }
+    }
+
     pub async fn decode_single_with_addr_fallback(&self, raw: &str) -> Result<Felt> {
         let decoded = self.decode_inner(raw, FallbackOption::Address).await?;
 
@@ -81,6 +120,25 @@ where
     async fn decode_inner(&self, raw: &str, fallback_option: FallbackOption) -> Result<Vec<Felt>> {
         if let Some(addr_name) = raw.strip_prefix("addr:") {
             Ok(vec![self.resolve_addr(addr_name).await?])
+        } else if let Some(field) = raw.strip_prefix("self:") {
+            let resolver = self.self_account_resolver.as_ref().ok_or_else(|| {
+                anyhow::anyhow!("the `self` scheme is only available with account options")
+            })?;
+
+            match field {
+                "address" => Ok(vec![resolver.address()?]),
+                "pubkey" => Ok(vec![resolver.public_key().await?]),
+                _ => Err(anyhow::anyhow!(
+                    "unknown self field \"{}\": expected `address` or `pubkey`",
+                    field
+                )),
+            }
+        } else if let Some(domain) = raw.strip_prefix("stark:") {
+            Ok(vec![
+                self.address_book_resolver
+                    .resolve_stark_name(domain)
+                    .await?,
+            ])
         } else if let Some(u256_str) = raw.strip_prefix("u256:") {
             let bigint = if let Some(hex_str) = u256_str.strip_prefix("0x") {
                 let unsigned_bytes = if hex_str.len() % 2 == 0 {
@@ -137,10 +195,7 @@ where
         } else if let Some(selector) = raw.strip_prefix("selector:") {
             Ok(vec![get_selector_from_name(selector)?])
         } else if let Some(storage) = raw.strip_prefix("storage:") {
-            if storage.contains('[') || storage.contains(']') {
-                anyhow::bail!("cannot resolve storage address: maps not supported yet")
-            }
-            Ok(vec![get_storage_var_address(storage, &[])?])
+            Ok(vec![self.resolve_storage_address(storage).await?])
         } else if let Some(byte_array) = raw.strip_prefix("bytearray:") {
             let raw_bytes = if let Some(str) = byte_array.strip_prefix("str:") {
                 str.as_bytes().to_vec()
@@ -152,27 +207,138 @@ where
             ByteArray::from(raw_bytes).encode(&mut serialized)?;
 
             Ok(serialized)
+        } else if let Some(array) = raw.strip_prefix("array:") {
+            let items = array
+                .strip_prefix('[')
+                .and_then(|array| array.strip_suffix(']'))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("invalid array \"{}\": expected [item1,item2,...]", array)
+                })?;
+            let items = split_array_items(items)?;
+
+            // The length prefix counts items, not the elements they resolve into
+            let mut serialized = vec![Felt::from(items.len())];
+            for item in items.into_iter() {
+                // Boxed as the decoding is recursive
+                serialized
+                    .append(&mut Box::pin(self.decode_inner(item, FallbackOption::None)).await?);
+            }
+
+            Ok(serialized)
+        } else if let Some(items) = raw.strip_prefix("pedersen:") {
+            let elements = self.decode_hash_inputs(items).await?;
+            Ok(vec![HashFunction::Pedersen.hash(&elements)?])
+        } else if let Some(items) = raw.strip_prefix("poseidon:") {
+            let elements = self.decode_hash_inputs(items).await?;
+            Ok(vec![HashFunction::Poseidon.hash(&elements)?])
+        } else if let Some(items) = raw.strip_prefix("keccak:") {
+            let elements = self.decode_hash_inputs(items).await?;
+            Ok(vec![HashFunction::Keccak.hash(&elements)?])
+        } else if let Some(amount) = raw.strip_prefix("amount:") {
+            let (decimals, amount) = amount.split_once(':').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "invalid amount \"{}\": expected <DECIMALS>:<AMOUNT>",
+                    amount
+                )
+            })?;
+            let decimals = decimals
+                .parse::<u8>()
+                .map_err(|_| anyhow::anyhow!("invalid decimals: {}", decimals))?;
+
+            parse_token_amount(amount, decimals)
+        } else if let Some(bool_str) = raw.strip_prefix("bool:") {
+            match bool_str {
+                "true" => Ok(vec![Felt::ONE]),
+                "false" => Ok(vec![Felt::ZERO]),
+                _ => Err(anyhow::anyhow!("invalid bool value: {}", bool_str)),
+            }
+        } else if let Some((bits, signed, int_str)) = parse_int_scheme(raw) {
+            Ok(vec![if signed {
+                parse_signed(int_str, bits)?
+            } else {
+                parse_unsigned(int_str, bits)?
+            }])
+        } else if let Some((decimals, amount)) = self.resolve_token_amount(raw).await? {
+            parse_token_amount(amount, decimals)
         } else {
             match raw.parse::<Felt>() {
                 Ok(value) => Ok(vec![value]),
                 Err(err) => match fallback_option {
+                    FallbackOption::Address if raw.ends_with(".stark") => Ok(vec![
+                        self.address_book_resolver.resolve_stark_name(raw).await?,
+                    ]),
                     FallbackOption::Address => match self.resolve_addr(raw).await {
                         Ok(value) => Ok(vec![value]),
                         Err(_) => Err(err.into()),
                     },
                     FallbackOption::Selector => Ok(vec![get_selector_from_name(raw)?]),
-                    FallbackOption::Storage => {
-                        if raw.contains('[') || raw.contains(']') {
-                            anyhow::bail!("cannot resolve storage address: maps not supported yet")
-                        }
-                        Ok(vec![get_storage_var_address(raw, &[])?])
-                    }
+                    FallbackOption::Storage => Ok(vec![self.resolve_storage_address(raw).await?]),
                     FallbackOption::None => Err(err.into()),
                 },
             }
         }
     }
 
+    /// Splits `<token>:<amount>` values where the token is an address book entry with decimals on
+    /// the current chain.
+    async fn resolve_token_amount<'a>(&self, raw: &'a str) -> Result<Option<(u8, &'a str)>> {
+        let (token, amount) = match raw.split_once(':') {
+            Some(pair) => pair,
+            None => return Ok(None),
+        };
+
+        // Storage expressions like `balances[addr:alice]` are never token names
+        if validate_name(token).is_err() {
+            return Ok(None);
+        }
+
+        Ok(self
+            .address_book_resolver
+            .resolve_token_decimals(token)
+            .await?
+            .map(|decimals| (decimals, amount)))
+    }
+
+    /// Resolves storage address expressions in the form of `name[key1][key2]+offset`, where map
+    /// keys are hashed the same way as Cairo storage does, and the offset is added to the final
+    /// address for accessing struct members. Keys are themselves decoded, so `u256` keys spanning
+    /// two elements are supported.
+    async fn resolve_storage_address(&self, raw: &str) -> Result<Felt> {
+        let expression = parse_storage_expression(raw)?;
+
+        let mut keys = vec![];
+        for key in expression.keys.iter() {
+            // Boxed as the decoding is recursive
+            let decoded = Box::pin(self.decode_inner(key, FallbackOption::None))
+                .await
+                .map_err(|err| anyhow::anyhow!("invalid storage key \"{}\": {}", key, err))?;
+            keys.extend(decoded);
+        }
+
+        Ok(get_storage_var_address(expression.name, &keys)? + Felt::from(expression.offset))
+    }
+
+    /// Resolves comma-separated hash inputs, optionally wrapped in brackets for nesting, into the
+    /// elements to be hashed.
+    async fn decode_hash_inputs(&self, raw: &str) -> Result<Vec<Felt>> {
+        let items = match raw
+            .strip_prefix('[')
+            .and_then(|items| items.strip_suffix(']'))
+        {
+            // Only unwraps when the brackets enclose everything
+            Some(items) if split_array_items(raw)?.len() == 1 => items,
+            _ => raw,
+        };
+
+        let mut elements = vec![];
+        for item in split_array_items(items)?.into_iter() {
+            // Boxed as the decoding is recursive
+            elements.append(&mut Box::pin(self.decode_inner(item, FallbackOption::None)).await?);
+        }
+
+        Ok(elements)
+    }
+
     async fn resolve_addr(&self, name: &str) -> Result<Felt> {
         self.address_book_resolver
             .resolve_name(name)
@@ -180,3 +346,377 @@ where
             .ok_or_else(|| anyhow::anyhow!("address book entry not found for \"{}\"", name))
     }
 }
+
+/// Splits comma-separated array items without breaking up nested arrays.
+fn split_array_items(raw: &str) -> Result<Vec<&str>> {
+    if raw.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut items = vec![];
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (ind, c) in raw.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| anyhow::anyhow!("invalid array: unexpected `]`"))?
+            }
+            ',' if depth == 0 => {
+                items.push(raw[start..ind].trim());
+                start = ind + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        anyhow::bail!("invalid array: unclosed `[`");
+    }
+    items.push(raw[start..].trim());
+
+    if items.iter().any(|item| item.is_empty()) {
+        anyhow::bail!("invalid array: empty item");
+    }
+
+    Ok(items)
+}
+
+/// Whether the name is taken by a built-in scheme, which always takes precedence over the amount
+/// scheme named after an address book token.
+pub fn is_scheme_name(name: &str) -> bool {
+    const SCHEMES: [&str; 15] = [
+        "addr",
+        "self",
+        "stark",
+        "u256",
+        "const",
+        "str",
+        "selector",
+        "storage",
+        "bytearray",
+        "array",
+        "pedersen",
+        "poseidon",
+        "keccak",
+        "amount",
+        "bool",
+    ];
+
+    SCHEMES.contains(&name) || parse_int_scheme(&format!("{name}:")).is_some()
+}
+
+/// Matches integer width schemes like `u8:` and `i128:`, returning the width and signedness.
+fn parse_int_scheme(raw: &str) -> Option<(u32, bool, &str)> {
+    let (scheme, int_str) = raw.split_once(':')?;
+
+    let (signed, bits) = match scheme.strip_prefix('u') {
+        Some(bits) => (false, bits),
+        None => (true, scheme.strip_prefix('i')?),
+    };
+    let bits = bits.parse::<u32>().ok()?;
+
+    matches!(bits, 8 | 16 | 32 | 64 | 128).then_some((bits, signed, int_str))
+}
+
+/// Parses non-negative integers in either decimal or hexadecimal.
+fn parse_integer(int_str: &str) -> Result<BigUint> {
+    let value = if int_str.starts_with("0x") {
+        Felt::from_hex(int_str)?
+    } else {
+        bigdecimal_to_felt(&BigDecimal::from_str(int_str)?, 0)?
+    };
+
+    Ok(BigUint::from_bytes_be(&value.to_bytes_be()))
+}
+
+fn parse_unsigned(int_str: &str, bits: u32) -> Result<Felt> {
+    let value = parse_integer(int_str)?;
+    if value.bits() > bits as u64 {
+        anyhow::bail!("value {} out of range for u{}", int_str, bits);
+    }
+
+    Ok(Felt::from_bytes_be_slice(&value.to_bytes_be()))
+}
+
+/// Negative values are encoded as their field element negation, the same way as Cairo does.
+fn parse_signed(int_str: &str, bits: u32) -> Result<Felt> {
+    let (negative, magnitude) = match int_str.strip_prefix('-') {
+        Some(magnitude) => (true, parse_integer(magnitude)?),
+        None => (false, parse_integer(int_str)?),
+    };
+
+    let limit = BigUint::from(1u32) << (bits - 1);
+    if (negative && magnitude > limit) || (!negative && magnitude >= limit) {
+        anyhow::bail!("value {} out of range for i{}", int_str, bits);
+    }
+
+    let magnitude = Felt::from_bytes_be_slice(&magnitude.to_bytes_be());
+    Ok(if negative {
+        Felt::ZERO - magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// Scales a decimal token amount into a `u256` represented as low and high 128 bits.
+fn parse_token_amount(amount: &str, decimals: u8) -> Result<Vec<Felt>> {
+    let amount =
+        BigDecimal::from_str(amount).map_err(|_| anyhow::anyhow!("invalid amount: {}", amount))?;
+    let scaled = bigdecimal_to_felt(&amount, decimals)?;
+
+    let scaled = BigUint::from_bytes_be(&scaled.to_bytes_be());
+    let u128_max_plus_1 = BigUint::from(1u32) << 128;
+
+    let low = &scaled % &u128_max_plus_1;
+    let high = &scaled / &u128_max_plus_1;
+
+    Ok(vec![
+        Felt::from_bytes_be_slice(&low.to_bytes_be()),
+        Felt::from_bytes_be_slice(&high.to_bytes_be()),
+    ])
+}
+
+fn parse_storage_expression(raw: &str) -> Result<StorageExpression<'_>> {
+    let name_end = raw.find(['[', ']', '+']).unwrap_or(raw.len());
+    let name = &raw[..name_end];
+    if name.is_empty() {
+        anyhow::bail!(
+            "invalid storage expression \"{}\": missing variable name",
+            raw
+        );
+    }
+
+    let mut keys = vec![];
+    let mut rest = &raw[name_end..];
+
+    while let Some(key_start) = rest.strip_prefix('[') {
+        // Keys can contain brackets themselves so we look for the matching one
+        let mut depth = 1usize;
+        let key_end = key_start
+            .char_indices()
+            .find_map(|(ind, c)| {
+                match c {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    _ => {}
+                }
+                (depth == 0).then_some(ind)
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!("invalid storage expression \"{}\": unclosed `[`", raw)
+            })?;
+
+        let key = &key_start[..key_end];
+        if key.is_empty() {
+            anyhow::bail!("invalid storage expression \"{}\": empty key", raw);
+        }
+
+        keys.push(key);
+        rest = &key_start[(key_end + 1)..];
+    }
+
+    let offset = if rest.is_empty() {
+        0
+    } else if let Some(offset) = rest.strip_prefix('+') {
+        let parsed = match offset.strip_prefix("0x") {
+            Some(hex_offset) => u64::from_str_radix(hex_offset, 16),
+            None => offset.parse::<u64>(),
+        };
+        parsed.map_err(|_| {
+            anyhow::anyhow!("invalid storage expression \"{}\": invalid offset", raw)
+        })?
+    } else {
+        anyhow::bail!(
+            "invalid storage expression \"{}\": unexpected \"{}\"",
+            raw,
+            rest
+        );
+    };
+
+    Ok(StorageExpression { name, keys, offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain_id::OfflineSource;
+
+    fn offline_decoder() -> FeltDecoder<OfflineSource> {
+        FeltDecoder::new(AddressBookResolver::new(OfflineSource))
+    }
+
+    #[test]
+    fn test_parse_storage_expression() {
+        let expression = parse_storage_expression("balances").unwrap();
+        assert_eq!(expression.name, "balances");
+        assert!(expression.keys.is_empty());
+        assert_eq!(expression.offset, 0);
+
+        let expression = parse_storage_expression("allowances[0x1][addr:alice]+1").unwrap();
+        assert_eq!(expression.name, "allowances");
+        assert_eq!(expression.keys, ["0x1", "addr:alice"]);
+        assert_eq!(expression.offset, 1);
+
+        let expression = parse_storage_expression("data[array:[1,2]]+0x10").unwrap();
+        assert_eq!(expression.name, "data");
+        assert_eq!(expression.keys, ["array:[1,2]"]);
+        assert_eq!(expression.offset, 16);
+    }
+
+    #[test]
+    fn test_parse_storage_expression_invalid() {
+        for raw in [
+            "",
+            "[0x1]",
+            "balances[0x1",
+            "balances[]",
+            "balances]",
+            "balances+",
+            "balances+x",
+            "balances[0x1]x",
+        ] {
+            assert!(parse_storage_expression(raw).is_err(), "{}", raw);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_storage_scheme() {
+        let decoder = offline_decoder();
+
+        // Variables without keys are at `sn_keccak(name)`, the same as selectors
+        assert_eq!(
+            decoder.decode_single("storage:transfer").await.unwrap(),
+            felt!("0x0083afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e")
+        );
+        assert_eq!(
+            decoder.decode_single("storage:transfer+2").await.unwrap(),
+            felt!("0x0083afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d130")
+        );
+
+        assert_eq!(
+            decoder
+                .decode_single("storage:allowances[0x1][0x2]+1")
+                .await
+                .unwrap(),
+            get_storage_var_address("allowances", &[Felt::ONE, Felt::TWO]).unwrap() + Felt::ONE
+        );
+
+        // Keys spanning multiple elements are hashed element by element
+        assert_eq!(
+            decoder
+                .decode_single("storage:balances[u256:1]")
+                .await
+                .unwrap(),
+            get_storage_var_address("balances", &[Felt::ONE, Felt::ZERO]).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_int_schemes() {
+        let decoder = offline_decoder();
+
+        for (raw, expected) in [
+            ("u8:255", Felt::from(255u64)),
+            ("u8:0xff", Felt::from(255u64)),
+            ("u16:0", Felt::ZERO),
+            (
+                "u128:340282366920938463463374607431768211455",
+                Felt::from(u128::MAX),
+            ),
+            ("i8:127", Felt::from(127u64)),
+            ("i8:-1", Felt::ZERO - Felt::ONE),
+            ("i8:-128", Felt::ZERO - Felt::from(128u64)),
+            ("i8:-0x80", Felt::ZERO - Felt::from(128u64)),
+            (
+                "i128:-170141183460469231731687303715884105728",
+                Felt::ZERO - Felt::from(1u128 << 127),
+            ),
+        ] {
+            assert_eq!(
+                decoder.decode(raw).await.unwrap(),
+                vec![expected],
+                "{}",
+                raw
+            );
+        }
+
+        for raw in [
+            "u8:256",
+            "u8:-1",
+            "u128:340282366920938463463374607431768211456",
+            "i8:128",
+            "i8:-129",
+            "i128:170141183460469231731687303715884105728",
+            "u8:1.5",
+            "u8:abc",
+        ] {
+            assert!(decoder.decode(raw).await.is_err(), "{}", raw);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bool_scheme() {
+        let decoder = offline_decoder();
+
+        assert_eq!(decoder.decode("bool:true").await.unwrap(), vec![Felt::ONE]);
+        assert_eq!(
+            decoder.decode("bool:false").await.unwrap(),
+            vec![Felt::ZERO]
+        );
+        assert!(decoder.decode("bool:1").await.is_err());
+        assert!(decoder.decode("bool:True").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_amount_scheme() {
+        let decoder = offline_decoder();
+
+        assert_eq!(
+            decoder.decode("amount:6:12.5").await.unwrap(),
+            vec![Felt::from(12_500_000u64), Felt::ZERO]
+        );
+        assert_eq!(
+            decoder.decode("amount:18:1.5").await.unwrap(),
+            vec![Felt::from(1_500_000_000_000_000_000u64), Felt::ZERO]
+        );
+        assert_eq!(
+            decoder.decode("amount:0:7").await.unwrap(),
+            vec![Felt::from(7u64), Felt::ZERO]
+        );
+        // Amounts exceeding 128 bits spill into the high part
+        assert_eq!(
+            decoder
+                .decode("amount:18:340282366920938463463.374607431768211456")
+                .await
+                .unwrap(),
+            vec![Felt::ZERO, Felt::ONE]
+        );
+
+        for raw in [
+            "amount:6:0.0000001",
+            "amount:6:-1",
+            "amount:6:abc",
+            "amount:256:1",
+            "amount:6",
+        ] {
+            assert!(decoder.decode(raw).await.is_err(), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_is_scheme_name() {
+        for name in [
+            "u8", "u128", "i8", "i64", "u256", "bool", "amount", "addr", "str",
+        ] {
+            assert!(is_scheme_name(name), "{}", name);
+        }
+
+        for name in ["strk", "eth", "u7", "u512", "i256", "u8x", "bool2"] {
+            assert!(!is_scheme_name(name), "{}", name);
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::fmt::{Display, Formatter};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 
 use anyhow::Result;
 use bigdecimal::BigDecimal;
@@ -6,7 +9,7 @@ use clap::{builder::PossibleValue, Parser, ValueEnum};
 use num_traits::ToPrimitive;
 use starknet::core::types::Felt;
 
-use crate::utils::bigdecimal_to_felt;
+use crate::{config::ProjectConfig, utils::bigdecimal_to_felt};
 
 // The user is most likely making a mistake for using a gas price higher than 1 STRK.
 const MAX_GAS_PRICE: u128 = 1000000000000000000;
@@ -74,7 +77,7 @@ pub struct StrkManualFeeSetting {
 }
 
 impl FeeArgs {
-    pub fn into_setting(self) -> Result<FeeSetting> {
+    pub fn into_setting(mut self, project_config: Option<&ProjectConfig>) -> Result<FeeSetting> {
         // These 3 flags are kept (and hidden) for now only to serve an error message. Since v0.4.0
         // it's no longer possible to pay fees with ETH.
         //
@@ -115,6 +118,13 @@ impl FeeArgs {
             }
         };
 
+        // Fee settings from the project file are ignored when only estimating fees
+        if !self.estimate_only {
+            if let Some(config) = project_config {
+                self.apply_project_settings(config)?;
+            }
+        }
+
         if self.estimate_only {
             if self.l1_gas.is_some()
                 || self.l1_gas_price.is_some()
@@ -186,6 +196,43 @@ impl FeeArgs {
     }
 }
 
+impl FeeArgs {
+    /// Fills in settings from the project file for resources not overridden from the command line.
+    fn apply_project_settings(&mut self, config: &ProjectConfig) -> Result<()> {
+        let fee = &config.fee;
+
+        if self.l1_gas.is_none() {
+            self.l1_gas = parse_project_value(&fee.l1_gas, "l1_gas", config)?;
+        }
+        if self.l2_gas.is_none() {
+            self.l2_gas = parse_project_value(&fee.l2_gas, "l2_gas", config)?;
+        }
+        if self.l1_data_gas.is_none() {
+            self.l1_data_gas = parse_project_value(&fee.l1_data_gas, "l1_data_gas", config)?;
+        }
+
+        // A price from the command line in either form replaces the one from the project file
+        if self.l1_gas_price.is_none() && self.l1_gas_price_raw.is_none() {
+            self.l1_gas_price = parse_project_value(&fee.l1_gas_price, "l1_gas_price", config)?;
+            self.l1_gas_price_raw =
+                parse_project_value(&fee.l1_gas_price_raw, "l1_gas_price_raw", config)?;
+        }
+        if self.l2_gas_price.is_none() && self.l2_gas_price_raw.is_none() {
+            self.l2_gas_price = parse_project_value(&fee.l2_gas_price, "l2_gas_price", config)?;
+            self.l2_gas_price_raw =
+                parse_project_value(&fee.l2_gas_price_raw, "l2_gas_price_raw", config)?;
+        }
+        if self.l1_data_gas_price.is_none() && self.l1_data_gas_price_raw.is_none() {
+            self.l1_data_gas_price =
+                parse_project_value(&fee.l1_data_gas_price, "l1_data_gas_price", config)?;
+            self.l1_data_gas_price_raw =
+                parse_project_value(&fee.l1_data_gas_price_raw, "l1_data_gas_price_raw", config)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl ValueEnum for FeeToken {
     fn value_variants<'a>() -> &'a [Self] {
         &[Self::Eth, Self::Strk]
@@ -272,3 +319,26 @@ fn resolve_gas_price_override(
         (None, None) => None,
     })
 }
+
+fn parse_project_value<T>(
+    value: &Option<String>,
+    key: &str,
+    config: &ProjectConfig,
+) -> Result<Option<T>>
+where
+    T: FromStr,
+{
+    value
+        .as_ref()
+        .map(|value| {
+            T::from_str(value).map_err(|_| {
+                anyhow::anyhow!(
+                    "invalid value \"{}\" for `fee.{}` in {}",
+                    value,
+                    key,
+                    config.path.display()
+                )
+            })
+        })
+        .transpose()
+}
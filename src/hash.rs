@@ -0,0 +1,65 @@
+use anyhow::Result;
+use clap::{builder::PossibleValue, ValueEnum};
+use starknet::core::{
+    crypto::{compute_hash_on_elements, pedersen_hash},
+    types::Felt,
+    utils::starknet_keccak,
+};
+use starknet_crypto::poseidon_hash_many;
+
+/// Hash functions over lists of field elements, shared by argument resolution and `starkli hash`.
+#[derive(Debug, Clone, Copy)]
+pub enum HashFunction {
+    Pedersen,
+    Poseidon,
+    Keccak,
+    ComputeHashOnElements,
+}
+
+impl HashFunction {
+    pub fn hash(&self, elements: &[Felt]) -> Result<Felt> {
+        Ok(match self {
+            Self::Pedersen => {
+                if elements.len() < 2 {
+                    anyhow::bail!("pedersen hash requires at least 2 elements");
+                }
+
+                // Chained the same way as hashing multiple values with `LegacyHash` in Cairo
+                elements[1..]
+                    .iter()
+                    .fold(elements[0], |acc, element| pedersen_hash(&acc, element))
+            }
+            Self::Poseidon => poseidon_hash_many(elements),
+            Self::Keccak => {
+                let bytes = elements
+                    .iter()
+                    .flat_map(|element| element.to_bytes_be())
+                    .collect::<Vec<_>>();
+                starknet_keccak(&bytes)
+            }
+            Self::ComputeHashOnElements => compute_hash_on_elements(elements),
+        })
+    }
+}
+
+impl ValueEnum for HashFunction {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Pedersen,
+            Self::Poseidon,
+            Self::Keccak,
+            Self::ComputeHashOnElements,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            Self::Pedersen => Some(PossibleValue::new("pedersen")),
+            Self::Poseidon => Some(PossibleValue::new("poseidon")),
+            Self::Keccak => Some(PossibleValue::new("keccak").alias("sn_keccak")),
+            Self::ComputeHashOnElements => Some(
+                PossibleValue::new("compute-hash-on-elements").alias("compute_hash_on_elements"),
+            ),
+        }
+    }
+}
@@ -4,23 +4,29 @@ use colored::Colorize;
 
 use crate::{provider::ProviderArgs, subcommands::*};
 
+mod abi;
 mod account;
 mod account_factory;
 mod address_book;
 mod block_id;
+mod calls_file;
 mod casm;
 mod chain_id;
 mod compiler;
+mod config;
 mod decode;
 mod error;
 mod fee;
+mod hash;
 mod hd_path;
 mod network;
 mod path;
 mod profile;
 mod provider;
 mod signer;
+mod starknet_id;
 mod subcommands;
+mod tx_hash;
 mod utils;
 mod verbosity;
 
@@ -59,7 +65,9 @@ enum Subcommands {
     Selector(Selector),
     #[clap(about = "Calculate class hash from any contract artifacts (Sierra, casm, legacy)")]
     ClassHash(ClassHash),
-    #[clap(about = "Extract contract ABI from a class artifact (Sierra or legacy)")]
+    #[clap(
+        about = "Extract contract ABI from a class artifact or a class on-chain (Sierra or legacy)"
+    )]
     Abi(Abi),
     #[clap(about = "Encode string into felt with the Cairo short string representation")]
     ToCairoString(ToCairoString),
@@ -67,6 +75,12 @@ enum Subcommands {
     ParseCairoString(ParseCairoString),
     #[clap(about = "Print the montgomery representation of a field element")]
     Mont(Mont),
+    #[clap(about = "Hash field elements with Pedersen, Poseidon or Starknet Keccak")]
+    Hash(Hash),
+    #[clap(about = "Compute contract addresses of deployments without network access")]
+    Address(Address),
+    #[clap(about = "Compute the hash of a v3 transaction without network access")]
+    TxHash(TxHash),
     //
     // JSON-RPC query client
     //
@@ -74,6 +88,8 @@ enum Subcommands {
     Call(Call),
     #[clap(alias = "tx", about = "Get Starknet transaction by hash")]
     Transaction(Transaction),
+    #[clap(about = "Decode account `__execute__` calldata into individual calls")]
+    DecodeCalls(DecodeCalls),
     #[clap(alias = "bn", about = "Get latest block number")]
     BlockNumber(BlockNumber),
     #[clap(about = "Get latest block hash")]
@@ -116,6 +132,19 @@ enum Subcommands {
     Syncing(Syncing),
     #[clap(about = "Get node spec version")]
     SpecVersion(SpecVersion),
+    #[clap(about = "Get the Starknet ID domain of an address")]
+    StarkName(StarkName),
+    //
+    // Profile management
+    //
+    #[clap(about = "Profile management commands")]
+    Profile(Profile),
+    #[clap(about = "Manage networks defined in profiles")]
+    Network(Network),
+    #[clap(about = "Inspect the effective configuration, including project-local starkli.toml")]
+    Config(Config),
+    #[clap(about = "Manage named contract addresses used by the addr: scheme")]
+    AddressBook(AddressBook),
     //
     // Signer management
     //
@@ -132,6 +161,13 @@ enum Subcommands {
     #[clap(about = "Account management commands")]
     Account(Account),
     //
+    // Off-chain signatures
+    //
+    #[clap(about = "Sign off-chain messages with an account")]
+    Sign(Sign),
+    #[clap(about = "Verify a signature locally or with the account contract")]
+    Verify(Verify),
+    //
     // Sending out transactions
     //
     #[clap(about = "Send an invoke transaction from an account contract")]
@@ -183,12 +219,16 @@ async fn run_command(cli: Cli) -> Result<()> {
         (false, Some(command)) => match command {
             Subcommands::Selector(cmd) => cmd.run(),
             Subcommands::ClassHash(cmd) => cmd.run(),
-            Subcommands::Abi(cmd) => cmd.run(),
+            Subcommands::Abi(cmd) => cmd.run().await,
             Subcommands::ToCairoString(cmd) => cmd.run(),
             Subcommands::ParseCairoString(cmd) => cmd.run(),
             Subcommands::Mont(cmd) => cmd.run(),
+            Subcommands::Hash(cmd) => cmd.run().await,
+            Subcommands::Address(cmd) => cmd.run().await,
+            Subcommands::TxHash(cmd) => cmd.run(),
             Subcommands::Call(cmd) => cmd.run().await,
             Subcommands::Transaction(cmd) => cmd.run().await,
+            Subcommands::DecodeCalls(cmd) => cmd.run().await,
             Subcommands::BlockNumber(cmd) => cmd.run().await,
             Subcommands::BlockHash(cmd) => cmd.run().await,
             Subcommands::Block(cmd) => cmd.run().await,
@@ -207,11 +247,18 @@ async fn run_command(cli: Cli) -> Result<()> {
             Subcommands::ClassAt(cmd) => cmd.run().await,
             Subcommands::Syncing(cmd) => cmd.run().await,
             Subcommands::SpecVersion(cmd) => cmd.run().await,
+            Subcommands::StarkName(cmd) => cmd.run().await,
+            Subcommands::Profile(cmd) => cmd.run(),
+            Subcommands::Network(cmd) => cmd.run().await,
+            Subcommands::Config(cmd) => cmd.run(),
+            Subcommands::AddressBook(cmd) => cmd.run().await,
             Subcommands::Signer(cmd) => cmd.run().await,
             #[cfg(feature = "ledger")]
             Subcommands::Ledger(cmd) => cmd.run().await,
             Subcommands::Eip2645(cmd) => cmd.run(),
             Subcommands::Account(cmd) => cmd.run().await,
+            Subcommands::Sign(cmd) => cmd.run().await,
+            Subcommands::Verify(cmd) => cmd.run().await,
             Subcommands::Invoke(cmd) => cmd.run().await,
             Subcommands::Declare(cmd) => cmd.run().await,
             Subcommands::Deploy(cmd) => cmd.run().await,
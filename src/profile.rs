@@ -2,10 +2,12 @@ use std::{
     fmt::Display,
     io::{Read, Write},
     path::PathBuf,
+    str::FromStr,
 };
 
 use anyhow::Result;
-use indexmap::IndexMap;
+use clap::{builder::PossibleValue, Parser, ValueEnum};
+use indexmap::{map::Entry, IndexMap};
 use serde::{de::Visitor, Deserialize, Serialize};
 use starknet::core::{
     types::Felt,
@@ -15,6 +17,18 @@ use url::Url;
 
 pub(crate) const DEFAULT_PROFILE_NAME: &str = "default";
 
+/// Profile selection shared by all commands that resolve or manage networks.
+#[derive(Debug, Clone, Parser)]
+pub struct ProfileArgs {
+    #[clap(
+        long = "profile",
+        global = true,
+        env = "STARKNET_PROFILE",
+        help = "Profile in which networks are resolved and managed [default: default]"
+    )]
+    profile: Option<String>,
+}
+
 #[derive(Debug, Default)]
 pub struct Profiles {
     pub profiles: IndexMap<String, Profile>,
@@ -39,6 +53,12 @@ pub struct Network {
     #[serde(default, skip_serializing_if = "is_false")]
     pub is_integration: bool,
     pub provider: NetworkProvider,
+    /// Account config file path or built-in account ID used when no account is supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Signer used when no signer option is supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<NetworkSigner>,
 }
 
 #[derive(Debug)]
@@ -47,12 +67,54 @@ pub enum NetworkProvider {
     Free(FreeProviderVendor),
 }
 
+/// JSON-RPC provider backed by one or more endpoints. Requests fail over to the next endpoint when
+/// the current one is unavailable.
 #[derive(Debug, Clone)]
 pub struct RpcProvider {
+    /// Never empty.
+    pub endpoints: Vec<RpcEndpoint>,
+    pub failover: FailoverStrategy,
+}
+
+/// An RPC endpoint as configured. The URL and header values may reference environment variables
+/// as `${ENV_VAR}` or `${ENV_VAR:-default}`, which are only expanded when the endpoint is used, so
+/// that secrets are never written back to the profiles file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RpcEndpoint {
+    #[serde(deserialize_with = "deserialize_url")]
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<HttpHeader>,
+}
+
+/// An [`RpcEndpoint`] with all environment variable references expanded.
+#[derive(Debug, Clone)]
+pub struct ExpandedRpcEndpoint {
     pub url: Url,
     pub headers: Vec<HttpHeader>,
 }
 
+/// The order in which endpoints of an [`RpcProvider`] are tried.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailoverStrategy {
+    /// Always start from the first endpoint, using the others only as fallbacks.
+    #[default]
+    Priority,
+    /// Rotate the starting endpoint for each request to spread the load.
+    RoundRobin,
+}
+
+/// Default signer source of a network. Unlike signer options on the command line, private keys
+/// are not supported as they'd be stored in plain text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", deny_unknown_fields, rename_all = "snake_case")]
+pub enum NetworkSigner {
+    Keystore { path: String },
+    Ledger { path: String },
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum FreeProviderVendor {
@@ -66,9 +128,23 @@ pub struct HttpHeader {
     pub value: String,
 }
 
+enum TemplateSegment<'a> {
+    Literal(&'a str),
+    EnvVar {
+        name: &'a str,
+        default: Option<&'a str>,
+    },
+}
+
 struct ChainIdVisitor;
 struct UrlVisitor;
 
+impl ProfileArgs {
+    pub fn name(&self) -> &str {
+        self.profile.as_deref().unwrap_or(DEFAULT_PROFILE_NAME)
+    }
+}
+
 impl Profiles {
     pub fn load() -> Result<Self> {
         let path = Self::get_profiles_path()?;
@@ -83,19 +159,30 @@ impl Profiles {
             Self::default()
         };
 
-        // Custom profile to be supported in the future
-        if loaded_profiles.profiles.len() > 1
-            || (loaded_profiles.profiles.len() == 1
-                && !loaded_profiles.profiles.contains_key(DEFAULT_PROFILE_NAME))
-        {
-            anyhow::bail!(
-                "invalid profiles: only the `default` profile is supported at the moment"
-            );
+        for profile_name in loaded_profiles.profiles.keys() {
+            if profile_name.is_empty() {
+                anyhow::bail!("invalid profiles: profile name must not be empty");
+            }
         }
 
         Ok(loaded_profiles)
     }
 
+    /// Gets the profile with the given name. The `default` profile is created if it doesn't exist,
+    /// while any other profile must have been defined by the user.
+    pub fn get_profile_mut(&mut self, name: &str) -> Result<&mut Profile> {
+        match self.profiles.entry(name.to_owned()) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                if name != DEFAULT_PROFILE_NAME {
+                    anyhow::bail!("profile `{}` is not defined", name);
+                }
+
+                Ok(entry.insert(Profile::default()))
+            }
+        }
+    }
+
     pub fn save(&self) -> Result<()> {
         let serialized = toml::to_string_pretty(self)?;
 
@@ -112,7 +199,7 @@ impl Profiles {
         Ok(())
     }
 
-    fn get_config_folder() -> Result<PathBuf> {
+    pub fn get_config_folder() -> Result<PathBuf> {
         #[cfg(target_arch = "wasm32")]
         {
             Ok(PathBuf::from("/home/dev/.config/starkli"))
@@ -136,6 +223,50 @@ impl Profiles {
     }
 }
 
+impl RpcEndpoint {
+    /// Creates an endpoint whose URL may reference environment variables.
+    pub fn new(url: String, headers: Vec<HttpHeader>) -> Result<Self> {
+        if !is_valid_url_template(&url) {
+            anyhow::bail!("invalid URL: {}", url);
+        }
+
+        Ok(Self { url, headers })
+    }
+
+    /// Whether the URL or any header value references environment variables.
+    pub fn references_env_vars(&self) -> bool {
+        self.url.contains("${")
+            || self
+                .headers
+                .iter()
+                .any(|header| header.value.contains("${"))
+    }
+
+    /// Expands environment variable references in the URL and header values.
+    pub fn expand(&self) -> Result<ExpandedRpcEndpoint> {
+        let expanded_url = expand_env_vars(&self.url)?;
+        let url = Url::parse(&expanded_url).map_err(|_| {
+            anyhow::anyhow!(
+                "invalid URL after expanding environment variables in \"{}\"",
+                self.url
+            )
+        })?;
+
+        let headers = self
+            .headers
+            .iter()
+            .map(|header| {
+                Ok(HttpHeader {
+                    name: header.name.clone(),
+                    value: expand_env_vars(&header.value)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ExpandedRpcEndpoint { url, headers })
+    }
+}
+
 impl Serialize for Profiles {
     fn serialize<S>(&self, serializer: S) -> std::prelude::v1::Result<S::Ok, S::Error>
     where
@@ -174,6 +305,20 @@ impl Serialize for NetworkProvider {
         #[serde(transparent)]
         struct RpcVariant<'a>(&'a str);
 
+        #[derive(Serialize)]
+        struct SingleRpcVariant<'a> {
+            r#type: &'static str,
+            url: &'a str,
+            headers: &'a [HttpHeader],
+        }
+
+        #[derive(Serialize)]
+        struct MultiRpcVariant<'a> {
+            r#type: &'static str,
+            endpoints: &'a [RpcEndpoint],
+            failover: &'a FailoverStrategy,
+        }
+
         #[derive(Serialize)]
         struct FreeVariant<'a> {
             r#type: &'static str,
@@ -181,7 +326,28 @@ impl Serialize for NetworkProvider {
         }
 
         match self {
-            Self::Rpc(value) => RpcVariant(value.url.as_ref()).serialize(serializer),
+            // Single-endpoint providers are written in the more concise legacy formats
+            Self::Rpc(value) => match value.endpoints.as_slice() {
+                // The shorthand cannot carry headers
+                [endpoint]
+                    if value.failover == FailoverStrategy::Priority
+                        && endpoint.headers.is_empty() =>
+                {
+                    RpcVariant(&endpoint.url).serialize(serializer)
+                }
+                [endpoint] if value.failover == FailoverStrategy::Priority => SingleRpcVariant {
+                    r#type: "rpc",
+                    url: &endpoint.url,
+                    headers: &endpoint.headers,
+                }
+                .serialize(serializer),
+                endpoints => MultiRpcVariant {
+                    r#type: "rpc",
+                    endpoints,
+                    failover: &value.failover,
+                }
+                .serialize(serializer),
+            },
             Self::Free(value) => FreeVariant {
                 r#type: "free",
                 vendor: value,
@@ -199,7 +365,7 @@ impl<'de> Deserialize<'de> for NetworkProvider {
         #[derive(Deserialize)]
         #[serde(deny_unknown_fields, untagged)]
         enum ShorthandOrTagged {
-            Shorthand(#[serde(deserialize_with = "deserialize_url")] Url),
+            Shorthand(#[serde(deserialize_with = "deserialize_url")] String),
             Tagged(Tagged),
         }
 
@@ -210,13 +376,18 @@ impl<'de> Deserialize<'de> for NetworkProvider {
             Free(FreeVariant),
         }
 
+        // Either `url` (with optional `headers`) for a single endpoint, or `endpoints`
         #[derive(Deserialize)]
         #[serde(deny_unknown_fields)]
         struct RpcVariant {
-            #[serde(deserialize_with = "deserialize_url")]
-            url: Url,
-            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            #[serde(default, deserialize_with = "deserialize_optional_url")]
+            url: Option<String>,
+            #[serde(default)]
             headers: Vec<HttpHeader>,
+            #[serde(default)]
+            endpoints: Vec<RpcEndpoint>,
+            #[serde(default)]
+            failover: FailoverStrategy,
         }
 
         #[derive(Deserialize)]
@@ -227,20 +398,111 @@ impl<'de> Deserialize<'de> for NetworkProvider {
 
         Ok(match ShorthandOrTagged::deserialize(deserializer)? {
             ShorthandOrTagged::Shorthand(value) => Self::Rpc(RpcProvider {
-                url: value,
-                headers: vec![],
+                endpoints: vec![RpcEndpoint {
+                    url: value,
+                    headers: vec![],
+                }],
+                failover: FailoverStrategy::Priority,
             }),
             ShorthandOrTagged::Tagged(value) => match value {
-                Tagged::Rpc(value) => Self::Rpc(RpcProvider {
-                    url: value.url,
-                    headers: value.headers,
-                }),
+                Tagged::Rpc(value) => {
+                    let endpoints = match (value.url, value.endpoints.is_empty()) {
+                        (Some(url), true) => vec![RpcEndpoint {
+                            url,
+                            headers: value.headers,
+                        }],
+                        (None, false) => {
+                            if !value.headers.is_empty() {
+                                return Err(serde::de::Error::custom(
+                                    "`headers` must be set on each item of `endpoints`",
+                                ));
+                            }
+
+                            value.endpoints
+                        }
+                        (Some(_), false) => {
+                            return Err(serde::de::Error::custom(
+                                "`url` and `endpoints` cannot be used at the same time",
+                            ))
+                        }
+                        (None, true) => {
+                            return Err(serde::de::Error::custom(
+                                "either `url` or `endpoints` must be set",
+                            ))
+                        }
+                    };
+
+                    Self::Rpc(RpcProvider {
+                        endpoints,
+                        failover: value.failover,
+                    })
+                }
                 Tagged::Free(value) => Self::Free(value.vendor),
             },
         })
     }
 }
 
+impl Display for NetworkProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rpc(value) => {
+                for (ind, endpoint) in value.endpoints.iter().enumerate() {
+                    if ind > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", endpoint.url)?;
+                }
+
+                Ok(())
+            }
+            Self::Free(value) => write!(f, "free RPC vendor {}", value),
+        }
+    }
+}
+
+impl ValueEnum for FailoverStrategy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Priority, Self::RoundRobin]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            Self::Priority => Some(PossibleValue::new("priority")),
+            Self::RoundRobin => Some(PossibleValue::new("round_robin").alias("round-robin")),
+        }
+    }
+}
+
+impl FromStr for HttpHeader {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, value) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("invalid HTTP header \"{}\": expected NAME:VALUE", s))?;
+
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("invalid HTTP header \"{}\": empty header name", s);
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            value: value.trim().to_owned(),
+        })
+    }
+}
+
+impl Display for NetworkSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Keystore { path } => write!(f, "keystore {}", path),
+            Self::Ledger { path } => write!(f, "Ledger {}", path),
+        }
+    }
+}
+
 impl Display for FreeProviderVendor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -271,7 +533,7 @@ impl Visitor<'_> for ChainIdVisitor {
 }
 
 impl Visitor<'_> for UrlVisitor {
-    type Value = Url;
+    type Value = String;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(formatter, "string")
@@ -281,9 +543,14 @@ impl Visitor<'_> for UrlVisitor {
     where
         E: serde::de::Error,
     {
-        Url::parse(v).map_err(|_| {
-            serde::de::Error::invalid_value(serde::de::Unexpected::Str(v), &"valid URL")
-        })
+        if is_valid_url_template(v) {
+            Ok(v.to_owned())
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(v),
+                &"valid URL",
+            ))
+        }
     }
 }
 
@@ -304,13 +571,165 @@ where
     deserializer.deserialize_str(ChainIdVisitor)
 }
 
-fn deserialize_url<'de, D>(deserializer: D) -> Result<Url, D::Error>
+fn deserialize_url<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     deserializer.deserialize_str(UrlVisitor)
 }
 
+fn deserialize_optional_url<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_url(deserializer).map(Some)
+}
+
+fn is_valid_url_template(url: &str) -> bool {
+    // URLs referencing environment variables can only be fully validated after expansion
+    if url.contains("${") {
+        parse_env_template(url).is_ok()
+    } else {
+        Url::parse(url).is_ok()
+    }
+}
+
+/// Expands `${ENV_VAR}` and `${ENV_VAR:-default}` references. As with shells, the default value
+/// is also used when the variable is set but empty.
+fn expand_env_vars(template: &str) -> Result<String> {
+    let mut expanded = String::new();
+
+    for segment in parse_env_template(template)? {
+        match segment {
+            TemplateSegment::Literal(value) => expanded.push_str(value),
+            TemplateSegment::EnvVar { name, default } => {
+                match (
+                    std::env::var(name).ok().filter(|value| !value.is_empty()),
+                    default,
+                ) {
+                    (Some(value), _) => expanded.push_str(&value),
+                    (None, Some(default)) => expanded.push_str(default),
+                    (None, None) => anyhow::bail!(
+                        "environment variable `{}` referenced in \"{}\" is not set",
+                        name,
+                        template
+                    ),
+                }
+            }
+        }
+    }
+
+    Ok(expanded)
+}
+
+fn parse_env_template(template: &str) -> Result<Vec<TemplateSegment<'_>>> {
+    let mut segments = vec![];
+    let mut remaining = template;
+
+    while let Some(start) = remaining.find("${") {
+        if start > 0 {
+            segments.push(TemplateSegment::Literal(&remaining[..start]));
+        }
+
+        let reference = &remaining[(start + 2)..];
+        let end = reference.find('}').ok_or_else(|| {
+            anyhow::anyhow!(
+                "unterminated environment variable reference in \"{}\"",
+                template
+            )
+        })?;
+
+        let (name, default) = match reference[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&reference[..end], None),
+        };
+
+        let is_valid_name = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_valid_name {
+            anyhow::bail!(
+                "invalid environment variable name \"{}\" in \"{}\"",
+                name,
+                template
+            );
+        }
+
+        segments.push(TemplateSegment::EnvVar { name, default });
+        remaining = &reference[(end + 1)..];
+    }
+
+    if !remaining.is_empty() {
+        segments.push(TemplateSegment::Literal(remaining));
+    }
+
+    Ok(segments)
+}
+
 fn is_false(value: &bool) -> bool {
     value == &false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_env_vars() {
+        std::env::set_var("STARKLI_TEST_PROFILE_API_KEY", "secret");
+        std::env::set_var("STARKLI_TEST_PROFILE_EMPTY", "");
+        std::env::remove_var("STARKLI_TEST_PROFILE_UNSET");
+
+        assert_eq!(
+            expand_env_vars("https://rpc.example.com/v0_8/${STARKLI_TEST_PROFILE_API_KEY}")
+                .unwrap(),
+            "https://rpc.example.com/v0_8/secret"
+        );
+        assert_eq!(
+            expand_env_vars("${STARKLI_TEST_PROFILE_UNSET:-http://localhost:5050}").unwrap(),
+            "http://localhost:5050"
+        );
+        assert_eq!(
+            expand_env_vars("Bearer ${STARKLI_TEST_PROFILE_EMPTY:-fallback}").unwrap(),
+            "Bearer fallback"
+        );
+        assert_eq!(
+            expand_env_vars(
+                "${STARKLI_TEST_PROFILE_API_KEY}-${STARKLI_TEST_PROFILE_API_KEY:-unused}"
+            )
+            .unwrap(),
+            "secret-secret"
+        );
+        assert_eq!(
+            expand_env_vars("${STARKLI_TEST_PROFILE_UNSET:-}").unwrap(),
+            ""
+        );
+        assert_eq!(
+            expand_env_vars("http://localhost:5050").unwrap(),
+            "http://localhost:5050"
+        );
+
+        assert!(expand_env_vars("${STARKLI_TEST_PROFILE_UNSET}").is_err());
+        assert!(expand_env_vars("${STARKLI_TEST_PROFILE_EMPTY}").is_err());
+    }
+
+    #[test]
+    fn test_parse_env_template_errors() {
+        assert!(parse_env_template("https://${API_KEY").is_err());
+        assert!(parse_env_template("https://${}").is_err());
+        assert!(parse_env_template("https://${1API_KEY}").is_err());
+        assert!(parse_env_template("https://${API-KEY}").is_err());
+        assert!(parse_env_template("https://${:-default}").is_err());
+    }
+
+    #[test]
+    fn test_is_valid_url_template() {
+        assert!(is_valid_url_template("https://rpc.example.com/${API_KEY}"));
+        assert!(is_valid_url_template("${RPC_URL:-http://localhost:5050}"));
+        assert!(is_valid_url_template("http://localhost:5050"));
+        assert!(!is_valid_url_template("not a url"));
+        assert!(!is_valid_url_template("https://rpc.example.com/${API_KEY"));
+    }
+}
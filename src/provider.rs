@@ -1,25 +1,29 @@
-use std::str::FromStr;
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
 use colored::Colorize;
-use indexmap::map::Entry;
 use starknet::{
-    core::types::*,
+    core::{types::*, utils::parse_cairo_short_string},
     macros::short_string,
     providers::{
-        jsonrpc::HttpTransport, JsonRpcClient, Provider, ProviderError, ProviderRequestData,
-        ProviderResponseData,
+        jsonrpc::{HttpTransport, HttpTransportError, JsonRpcClientError},
+        JsonRpcClient, Provider, ProviderError, ProviderRequestData, ProviderResponseData,
     },
 };
 use tokio::sync::OnceCell;
 use url::Url;
 
 use crate::{
+    config::{ProjectConfig, ProjectConfigSource},
     network::Network,
     profile::{
-        FreeProviderVendor, NetworkProvider, Profile, Profiles, RpcProvider, DEFAULT_PROFILE_NAME,
+        FailoverStrategy, FreeProviderVendor, NetworkProvider, NetworkSigner, ProfileArgs,
+        Profiles, RpcEndpoint, RpcProvider,
     },
     JSON_RPC_VERSION,
 };
@@ -37,19 +41,46 @@ pub struct ProviderArgs {
     rpc: Option<Url>,
     #[clap(long = "network", env = "STARKNET_NETWORK", help = "Starknet network")]
     network: Option<String>,
+    #[clap(flatten)]
+    profile: ProfileArgs,
+    #[clap(
+        long,
+        help = "Do not check that the RPC endpoint serves the chain ID configured for the \
+        network before sending transactions"
+    )]
+    skip_chain_id_check: bool,
 }
 
 pub struct ExtendedProvider {
-    provider: JsonRpcClient<HttpTransport>,
+    /// Never empty.
+    endpoints: Vec<ProviderEndpoint>,
+    failover: FailoverStrategy,
+    /// Index of the endpoint to start with for the next request in round-robin mode.
+    next_endpoint: AtomicUsize,
     rpc_version: OnceCell<String>,
+    /// Chain ID configured for the network, if any, to be checked before sending transactions.
+    network_chain_id: Option<Felt>,
+    chain_id_verified: OnceCell<()>,
+    /// Default account configured for the network, if any.
+    network_account: Option<String>,
+    /// Default signer configured for the network, if any.
+    network_signer: Option<NetworkSigner>,
+    /// The project file discovered when resolving the provider, if any.
+    project_config: Option<&'static ProjectConfig>,
+}
+
+struct ProviderEndpoint {
+    /// The URL as configured, which is safe to display as environment variables are not expanded.
+    url: String,
+    provider: JsonRpcClient<HttpTransport>,
 }
 
 impl ProviderArgs {
     pub fn into_provider(self) -> Result<ExtendedProvider> {
-        Ok(match (self.rpc, self.network) {
-            (Some(rpc), None) => {
-                ExtendedProvider::new(JsonRpcClient::new(HttpTransport::new(rpc)), None)
-            }
+        let project_config = ProjectConfig::load()?;
+
+        let mut provider = match (self.rpc, self.network) {
+            (Some(rpc), None) => ExtendedProvider::new(RpcProvider::from_url(rpc), None)?,
             (Some(rpc), Some(_)) => {
                 eprintln!(
                     "{}",
@@ -59,42 +90,73 @@ impl ProviderArgs {
                         .bright_magenta()
                 );
 
-                ExtendedProvider::new(JsonRpcClient::new(HttpTransport::new(rpc)), None)
+                ExtendedProvider::new(RpcProvider::from_url(rpc), None)?
             }
-            (None, Some(network)) => Self::resolve_network(&network)?,
-            (None, None) => {
-                eprintln!(
-                    "{}",
-                    "WARNING: you're using neither --rpc (STARKNET_RPC) nor --network \
-                    (STARKNET_NETWORK). The `sepolia` network is used by default. See \
-                    https://book.starkli.rs/providers for more details."
-                        .bright_magenta()
-                );
-
-                Self::resolve_network("sepolia")?
+            (None, Some(network)) => {
+                Self::resolve_network(self.profile.name(), &network, project_config)?
             }
-        })
+            (None, None) => match project_config.and_then(|config| config.network.as_deref()) {
+                Some(network) => {
+                    Self::resolve_network(self.profile.name(), network, project_config)?
+                }
+                None => {
+                    eprintln!(
+                        "{}",
+                        "WARNING: you're using neither --rpc (STARKNET_RPC) nor --network \
+                        (STARKNET_NETWORK), and no network is set in starkli.toml. The `sepolia` \
+                        network is used by default. See https://book.starkli.rs/providers for \
+                        more details."
+                            .bright_magenta()
+                    );
+
+                    Self::resolve_network(self.profile.name(), "sepolia", project_config)?
+                }
+            },
+        };
+
+        if self.skip_chain_id_check {
+            provider.network_chain_id = None;
+        }
+        provider.project_config = project_config;
+
+        Ok(provider)
     }
 
-    pub fn resolve_network(network: &str) -> Result<ExtendedProvider> {
+    pub fn resolve_network(
+        profile: &str,
+        network: &str,
+        project_config: Option<&ProjectConfig>,
+    ) -> Result<ExtendedProvider> {
+        // Networks from the project file are used as is and never persisted. Well-known networks
+        // are already rejected when loading the project file. Shadowing networks from the user
+        // profiles is rejected too, so that a cloned repository can't silently redirect them.
+        if let Some(project_network) =
+            project_config.and_then(|config| config.networks.get(network))
+        {
+            let profiles = Profiles::load()?;
+            if profiles
+                .profiles
+                .values()
+                .any(|profile| profile.networks.contains_key(network))
+            {
+                anyhow::bail!(
+                    "network `{}` is defined in both the project file and the user profiles. \
+                    Rename the one in the project file to use it.",
+                    network
+                );
+            }
+
+            return Self::network_to_provider(network, project_network);
+        }
+
         // TODO: move lazy profile loading to a higher level context
         let mut profiles = Profiles::load()?;
 
         // We save the profiles only when changes are made
         let mut made_changes = false;
 
-        // The only profile supported right now is the `default` profile. We create it if it
-        // doesn't exist.
-        let matched_profile = match profiles.profiles.entry(DEFAULT_PROFILE_NAME.to_owned()) {
-            Entry::Occupied(entry) => entry.into_mut(),
-            Entry::Vacant(entry) => {
-                made_changes = true;
-
-                entry.insert(Profile {
-                    networks: Default::default(),
-                })
-            }
-        };
+        // A newly created `default` profile is only saved along with networks added below
+        let matched_profile = profiles.get_profile_mut(profile)?;
 
         let matched_network = match matched_profile.networks.get_mut(network) {
             Some(network) => {
@@ -152,6 +214,8 @@ impl ProviderArgs {
                                         provider: NetworkProvider::Free(choose_vendor(
                                             &builtin_network,
                                         )),
+                                        account: None,
+                                        signer: None,
                                     },
                                     Network::Sepolia => crate::profile::Network {
                                         name: Some("Starknet Sepolia Testnet".into()),
@@ -160,6 +224,8 @@ impl ProviderArgs {
                                         provider: NetworkProvider::Free(choose_vendor(
                                             &builtin_network,
                                         )),
+                                        account: None,
+                                        signer: None,
                                     },
                                     Network::SepoliaIntegration => {
                                         anyhow::bail!(
@@ -182,9 +248,10 @@ impl ProviderArgs {
                     }
                     Err(_) => {
                         anyhow::bail!(
-                            "network `{}` is not configured in the active profile, and it's not a \
+                            "network `{}` is not configured in profile `{}`, and it's not a \
                             well-known network",
-                            network
+                            network,
+                            profile
                         );
                     }
                 }
@@ -209,21 +276,33 @@ impl ProviderArgs {
             made_changes = true;
         }
 
-        let (rpc_config, rpc_version) = match &matched_network.provider {
+        let provider = Self::network_to_provider(network, matched_network)?;
+
+        if made_changes {
+            profiles.save()?;
+        }
+
+        Ok(provider)
+    }
+
+    fn network_to_provider(
+        network: &str,
+        network_config: &crate::profile::Network,
+    ) -> Result<ExtendedProvider> {
+        let (rpc_config, rpc_version) = match &network_config.provider {
             NetworkProvider::Rpc(rpc) => (rpc.clone(), None),
             NetworkProvider::Free(vendor) => {
                 let url = match vendor {
-                    FreeProviderVendor::Blast => {
-                        if matched_network.chain_id == CHAIN_ID_MAINNET {
+                    // Nethermind has ended its free service and is always replaced by Blast
+                    FreeProviderVendor::Blast | FreeProviderVendor::Nethermind => {
+                        if network_config.chain_id == CHAIN_ID_MAINNET {
                             Some("https://starknet-mainnet.public.blastapi.io/rpc/v0_8")
-                        } else if matched_network.chain_id == CHAIN_ID_SEPOLIA {
+                        } else if network_config.chain_id == CHAIN_ID_SEPOLIA {
                             Some("https://starknet-sepolia.public.blastapi.io/rpc/v0_8")
                         } else {
                             None
                         }
                     }
-                    // Nethermind is always changed into Blast
-                    FreeProviderVendor::Nethermind => unreachable!(),
                 };
 
                 let url = match url {
@@ -233,49 +312,187 @@ impl ProviderArgs {
                     }
                     None => {
                         anyhow::bail!(
-                            "invalid network in profile: chain ID {:#x} is not supported by \
-                            vendor {}",
-                            matched_network.chain_id,
+                            "invalid network `{}`: chain ID {:#x} is not supported by vendor {}",
+                            network,
+                            network_config.chain_id,
                             vendor
                         );
                     }
                 };
 
                 (
-                    RpcProvider {
-                        url,
-                        headers: vec![],
-                    },
+                    RpcProvider::from_url(url),
                     // We always make sure to use the right version for free RPC vendors
                     Some(JSON_RPC_VERSION.into()),
                 )
             }
         };
 
-        let mut transport = HttpTransport::new(rpc_config.url);
-        for header in rpc_config.headers.iter() {
-            transport.add_header(header.name.clone(), header.value.clone());
-        }
-
-        let provider = ExtendedProvider::new(JsonRpcClient::new(transport), rpc_version);
-
-        if made_changes {
-            profiles.save()?;
-        }
+        let mut provider = ExtendedProvider::new(rpc_config, rpc_version).map_err(|err| {
+            anyhow::anyhow!(
+                "invalid provider settings for network `{}`: {}",
+                network,
+                err
+            )
+        })?;
+        provider.network_chain_id = Some(network_config.chain_id);
+        provider.network_account = network_config.account.clone();
+        provider.network_signer = network_config.signer.clone();
 
         Ok(provider)
     }
 }
 
 impl ExtendedProvider {
-    pub fn new(provider: JsonRpcClient<HttpTransport>, rpc_version: Option<String>) -> Self {
-        Self {
-            provider,
+    pub fn new(rpc: RpcProvider, rpc_version: Option<String>) -> Result<Self> {
+        Ok(Self {
+            endpoints: rpc
+                .endpoints
+                .into_iter()
+                .map(|endpoint| {
+                    let expanded = endpoint.expand()?;
+
+                    let mut transport = HttpTransport::new(expanded.url);
+                    for header in expanded.headers.into_iter() {
+                        transport.add_header(header.name, header.value);
+                    }
+
+                    Ok(ProviderEndpoint {
+                        url: endpoint.url,
+                        provider: JsonRpcClient::new(transport),
+                    })
+                })
+                .collect::<Result<_>>()?,
+            failover: rpc.failover,
+            next_endpoint: AtomicUsize::new(0),
             rpc_version: match rpc_version {
                 Some(rpc_version) => OnceCell::from(rpc_version),
                 None => OnceCell::new(),
             },
+            network_chain_id: None,
+            chain_id_verified: OnceCell::new(),
+            network_account: None,
+            network_signer: None,
+            project_config: None,
+        })
+    }
+
+    /// Default account of the network this provider is resolved from.
+    pub fn network_account(&self) -> Option<&str> {
+        self.network_account.as_deref()
+    }
+
+    /// Default signer of the network this provider is resolved from.
+    pub fn network_signer(&self) -> Option<&NetworkSigner> {
+        self.network_signer.as_ref()
+    }
+
+    /// The project file discovered when this provider is resolved.
+    pub fn project_config(&self) -> Option<&'static ProjectConfig> {
+        self.project_config
+    }
+
+    /// Checks that the endpoint serves the chain ID configured for the network. This guards against
+    /// misconfigured URLs sending transactions to the wrong network. The check is only performed
+    /// once, and is skipped for providers not resolved from a network.
+    pub async fn verify_chain_id(&self) -> Result<()> {
+        let network_chain_id = match self.network_chain_id {
+            Some(chain_id) => chain_id,
+            None => return Ok(()),
+        };
+
+        self.chain_id_verified
+            .get_or_try_init(|| async {
+                let endpoint_chain_id = self
+                    .chain_id()
+                    .await
+                    .map_err(|err| anyhow::anyhow!("unable to get chain id: {err}"))?;
+
+                if endpoint_chain_id != network_chain_id {
+                    anyhow::bail!(
+                        "chain ID mismatch: the network is configured with {}, but the RPC \
+                        endpoint serves {}. Check the network settings, or use \
+                        --skip-chain-id-check to send the transaction anyway.",
+                        display_chain_id(&network_chain_id),
+                        display_chain_id(&endpoint_chain_id)
+                    );
+                }
+
+                Ok(())
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Endpoints in the order they should be tried for the next request.
+    fn endpoint_order(&self) -> impl Iterator<Item = &ProviderEndpoint> {
+        let start = match self.failover {
+            FailoverStrategy::Priority => 0,
+            FailoverStrategy::RoundRobin => {
+                self.next_endpoint.fetch_add(1, Ordering::Relaxed) % self.endpoints.len()
+            }
+        };
+
+        self.endpoints
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(self.endpoints.len())
+    }
+}
+
+impl RpcProvider {
+    /// Creates a provider with a single endpoint without custom headers.
+    pub fn from_url(url: Url) -> Self {
+        Self {
+            endpoints: vec![RpcEndpoint {
+                url: url.to_string(),
+                headers: vec![],
+            }],
+            failover: FailoverStrategy::Priority,
+        }
+    }
+}
+
+/// Sends the same request to each endpoint in turn, until one of them returns either a response or
+/// an error that switching endpoints wouldn't fix.
+///
+/// Transactions must not be broadcast twice, so they're only sent to the next endpoint when the
+/// failure guarantees that the previous endpoint never received them.
+macro_rules! with_failover {
+    ($self:ident, |$provider:ident| $request:expr) => {
+        with_failover!($self, is_endpoint_error, |$provider| $request)
+    };
+    ($self:ident, $should_fail_over:ident, |$provider:ident| $request:expr) => {{
+        let mut endpoints = $self.endpoint_order().peekable();
+
+        loop {
+            // There's always at least one endpoint
+            let endpoint = endpoints.next().unwrap();
+            let $provider = &endpoint.provider;
+
+            match $request.await {
+                Err(err) if $should_fail_over(&err) => match endpoints.peek() {
+                    Some(next_endpoint) => {
+                        log::warn!(
+                            "request to {} failed ({}), failing over to {}",
+                            endpoint.url,
+                            err,
+                            next_endpoint.url
+                        );
+                    }
+                    None => break Err(err),
+                },
+                result => break result,
+            }
         }
+    }};
+}
+
+impl ProjectConfigSource for ExtendedProvider {
+    fn project_config(&self) -> Option<&ProjectConfig> {
+        self.project_config
     }
 }
 
@@ -283,7 +500,7 @@ impl ExtendedProvider {
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 impl Provider for ExtendedProvider {
     async fn spec_version(&self) -> Result<String, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::spec_version(&self.provider).await
+        with_failover!(self, |provider| provider.spec_version())
     }
 
     async fn get_block_with_tx_hashes<B>(
@@ -293,11 +510,8 @@ impl Provider for ExtendedProvider {
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_block_with_tx_hashes(
-            &self.provider,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_block_with_tx_hashes(&block_id))
     }
 
     async fn get_block_with_txs<B>(
@@ -307,8 +521,7 @@ impl Provider for ExtendedProvider {
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_block_with_txs(&self.provider, block_id)
-            .await
+        with_failover!(self, |provider| provider.get_block_with_txs(&block_id))
     }
 
     async fn get_block_with_receipts<B>(
@@ -318,11 +531,7 @@ impl Provider for ExtendedProvider {
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_block_with_receipts(
-            &self.provider,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider.get_block_with_receipts(&block_id))
     }
 
     async fn get_state_update<B>(
@@ -332,7 +541,7 @@ impl Provider for ExtendedProvider {
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_state_update(&self.provider, block_id).await
+        with_failover!(self, |provider| provider.get_state_update(&block_id))
     }
 
     async fn get_storage_at<A, K, B>(
@@ -346,24 +555,19 @@ impl Provider for ExtendedProvider {
         K: AsRef<Felt> + Send + Sync,
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_storage_at(
-            &self.provider,
-            contract_address,
-            key,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider.get_storage_at(
+            &contract_address,
+            &key,
+            &block_id
+        ))
     }
 
     async fn get_messages_status(
         &self,
         transaction_hash: Hash256,
     ) -> Result<Vec<MessageWithStatus>, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::get_messages_status(
-            &self.provider,
-            transaction_hash,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_messages_status(transaction_hash))
     }
 
     async fn get_transaction_status<H>(
@@ -373,11 +577,8 @@ impl Provider for ExtendedProvider {
     where
         H: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_transaction_status(
-            &self.provider,
-            transaction_hash,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_transaction_status(&transaction_hash))
     }
 
     async fn get_transaction_by_hash<H>(
@@ -387,11 +588,8 @@ impl Provider for ExtendedProvider {
     where
         H: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_transaction_by_hash(
-            &self.provider,
-            transaction_hash,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_transaction_by_hash(&transaction_hash))
     }
 
     async fn get_transaction_by_block_id_and_index<B>(
@@ -402,12 +600,8 @@ impl Provider for ExtendedProvider {
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_transaction_by_block_id_and_index(
-            &self.provider,
-            block_id,
-            index,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_transaction_by_block_id_and_index(&block_id, index))
     }
 
     async fn get_transaction_receipt<H>(
@@ -417,11 +611,8 @@ impl Provider for ExtendedProvider {
     where
         H: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_transaction_receipt(
-            &self.provider,
-            transaction_hash,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_transaction_receipt(&transaction_hash))
     }
 
     async fn get_class<B, H>(
@@ -433,8 +624,7 @@ impl Provider for ExtendedProvider {
         B: AsRef<BlockId> + Send + Sync,
         H: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_class(&self.provider, block_id, class_hash)
-            .await
+        with_failover!(self, |provider| provider.get_class(&block_id, &class_hash))
     }
 
     async fn get_class_hash_at<B, A>(
@@ -446,12 +636,8 @@ impl Provider for ExtendedProvider {
         B: AsRef<BlockId> + Send + Sync,
         A: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_class_hash_at(
-            &self.provider,
-            block_id,
-            contract_address,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_class_hash_at(&block_id, &contract_address))
     }
 
     async fn get_class_at<B, A>(
@@ -463,23 +649,16 @@ impl Provider for ExtendedProvider {
         B: AsRef<BlockId> + Send + Sync,
         A: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_class_at(
-            &self.provider,
-            block_id,
-            contract_address,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_class_at(&block_id, &contract_address))
     }
 
     async fn get_block_transaction_count<B>(&self, block_id: B) -> Result<u64, ProviderError>
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_block_transaction_count(
-            &self.provider,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_block_transaction_count(&block_id))
     }
 
     async fn call<R, B>(&self, request: R, block_id: B) -> Result<Vec<Felt>, ProviderError>
@@ -487,7 +666,7 @@ impl Provider for ExtendedProvider {
         R: AsRef<FunctionCall> + Send + Sync,
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::call(&self.provider, request, block_id).await
+        with_failover!(self, |provider| provider.call(&request, &block_id))
     }
 
     async fn estimate_fee<R, S, B>(
@@ -525,13 +704,11 @@ impl Provider for ExtendedProvider {
             );
         }
 
-        <JsonRpcClient<HttpTransport> as Provider>::estimate_fee(
-            &self.provider,
-            request,
-            simulation_flags,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider.estimate_fee(
+            &request,
+            &simulation_flags,
+            &block_id
+        ))
     }
 
     async fn estimate_message_fee<M, B>(
@@ -543,28 +720,24 @@ impl Provider for ExtendedProvider {
         M: AsRef<MsgFromL1> + Send + Sync,
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::estimate_message_fee(
-            &self.provider,
-            message,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .estimate_message_fee(&message, &block_id))
     }
 
     async fn block_number(&self) -> Result<u64, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::block_number(&self.provider).await
+        with_failover!(self, |provider| provider.block_number())
     }
 
     async fn block_hash_and_number(&self) -> Result<BlockHashAndNumber, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::block_hash_and_number(&self.provider).await
+        with_failover!(self, |provider| provider.block_hash_and_number())
     }
 
     async fn chain_id(&self) -> Result<Felt, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::chain_id(&self.provider).await
+        with_failover!(self, |provider| provider.chain_id())
     }
 
     async fn syncing(&self) -> Result<SyncStatusType, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::syncing(&self.provider).await
+        with_failover!(self, |provider| provider.syncing())
     }
 
     async fn get_events(
@@ -573,13 +746,11 @@ impl Provider for ExtendedProvider {
         continuation_token: Option<String>,
         chunk_size: u64,
     ) -> Result<EventsPage, ProviderError> {
-        <JsonRpcClient<HttpTransport> as Provider>::get_events(
-            &self.provider,
-            filter,
-            continuation_token,
-            chunk_size,
-        )
-        .await
+        with_failover!(self, |provider| provider.get_events(
+            filter.clone(),
+            continuation_token.clone(),
+            chunk_size
+        ))
     }
 
     async fn get_nonce<B, A>(&self, block_id: B, contract_address: A) -> Result<Felt, ProviderError>
@@ -587,12 +758,8 @@ impl Provider for ExtendedProvider {
         B: AsRef<BlockId> + Send + Sync,
         A: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_nonce(
-            &self.provider,
-            block_id,
-            contract_address,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .get_nonce(&block_id, &contract_address))
     }
 
     async fn get_storage_proof<B, H, A, K>(
@@ -608,14 +775,12 @@ impl Provider for ExtendedProvider {
         A: AsRef<[Felt]> + Send + Sync,
         K: AsRef<[ContractStorageKeys]> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::get_storage_proof(
-            &self.provider,
-            block_id,
-            class_hashes,
-            contract_addresses,
-            contracts_storage_keys,
-        )
-        .await
+        with_failover!(self, |provider| provider.get_storage_proof(
+            &block_id,
+            &class_hashes,
+            &contract_addresses,
+            &contracts_storage_keys
+        ))
     }
 
     async fn add_invoke_transaction<I>(
@@ -625,11 +790,8 @@ impl Provider for ExtendedProvider {
     where
         I: AsRef<BroadcastedInvokeTransaction> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::add_invoke_transaction(
-            &self.provider,
-            invoke_transaction,
-        )
-        .await
+        with_failover!(self, is_unsent_request_error, |provider| provider
+            .add_invoke_transaction(&invoke_transaction))
     }
 
     async fn add_declare_transaction<D>(
@@ -639,11 +801,8 @@ impl Provider for ExtendedProvider {
     where
         D: AsRef<BroadcastedDeclareTransaction> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::add_declare_transaction(
-            &self.provider,
-            declare_transaction,
-        )
-        .await
+        with_failover!(self, is_unsent_request_error, |provider| provider
+            .add_declare_transaction(&declare_transaction))
     }
 
     async fn add_deploy_account_transaction<D>(
@@ -653,11 +812,8 @@ impl Provider for ExtendedProvider {
     where
         D: AsRef<BroadcastedDeployAccountTransaction> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::add_deploy_account_transaction(
-            &self.provider,
-            deploy_account_transaction,
-        )
-        .await
+        with_failover!(self, is_unsent_request_error, |provider| provider
+            .add_deploy_account_transaction(&deploy_account_transaction))
     }
 
     async fn trace_transaction<H>(
@@ -667,11 +823,8 @@ impl Provider for ExtendedProvider {
     where
         H: AsRef<Felt> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::trace_transaction(
-            &self.provider,
-            transaction_hash,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .trace_transaction(&transaction_hash))
     }
 
     async fn simulate_transactions<B, T, S>(
@@ -685,13 +838,11 @@ impl Provider for ExtendedProvider {
         T: AsRef<[BroadcastedTransaction]> + Send + Sync,
         S: AsRef<[SimulationFlag]> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::simulate_transactions(
-            &self.provider,
-            block_id,
-            transactions,
-            simulation_flags,
-        )
-        .await
+        with_failover!(self, |provider| provider.simulate_transactions(
+            &block_id,
+            &transactions,
+            &simulation_flags
+        ))
     }
 
     async fn trace_block_transactions<B>(
@@ -701,11 +852,8 @@ impl Provider for ExtendedProvider {
     where
         B: AsRef<BlockId> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::trace_block_transactions(
-            &self.provider,
-            block_id,
-        )
-        .await
+        with_failover!(self, |provider| provider
+            .trace_block_transactions(&block_id))
     }
 
     async fn batch_requests<R>(
@@ -715,6 +863,102 @@ impl Provider for ExtendedProvider {
     where
         R: AsRef<[ProviderRequestData]> + Send + Sync,
     {
-        <JsonRpcClient<HttpTransport> as Provider>::batch_requests(&self.provider, requests).await
+        // Batches containing transactions are subject to the same restrictions as the transactions
+        if requests.as_ref().iter().any(|request| {
+            matches!(
+                request,
+                ProviderRequestData::AddInvokeTransaction(_)
+                    | ProviderRequestData::AddDeclareTransaction(_)
+                    | ProviderRequestData::AddDeployAccountTransaction(_)
+            )
+        }) {
+            with_failover!(self, is_unsent_request_error, |provider| provider
+                .batch_requests(&requests))
+        } else {
+            with_failover!(self, |provider| provider.batch_requests(&requests))
+        }
+    }
+}
+
+/// Checks whether an error is caused by the endpoint itself (connectivity, rate limiting, or server
+/// errors) rather than by the request, in which case another endpoint might succeed. JSON-RPC
+/// errors and responses that fail to deserialize are returned as is.
+fn is_endpoint_error(err: &ProviderError) -> bool {
+    match err {
+        ProviderError::RateLimited => true,
+        // Most HTTP 5xx responses don't have JSON bodies, and thus also fail in the transport
+        ProviderError::Other(err) => matches!(
+            err.as_any()
+                .downcast_ref::<JsonRpcClientError<HttpTransportError>>(),
+            Some(JsonRpcClientError::TransportError(_))
+        ),
+        _ => false,
+    }
+}
+
+/// Checks whether a request failed without ever reaching the endpoint, which is the only case where
+/// it's safe to send a transaction to another endpoint. Other failures, such as timeouts, are
+/// ambiguous as the transaction might have been accepted already.
+fn is_unsent_request_error(err: &ProviderError) -> bool {
+    match err {
+        ProviderError::RateLimited => true,
+        ProviderError::Other(err) => match err
+            .as_any()
+            .downcast_ref::<JsonRpcClientError<HttpTransportError>>()
+        {
+            Some(JsonRpcClientError::TransportError(HttpTransportError::Reqwest(err))) => {
+                // Connection failures can't be told apart from other failures in the browser
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    err.is_connect()
+                }
+                #[cfg(target_arch = "wasm32")]
+                {
+                    let _ = err;
+                    false
+                }
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn display_chain_id(chain_id: &Felt) -> String {
+    match parse_cairo_short_string(chain_id) {
+        Ok(value) => value,
+        Err(_) => format!("{chain_id:#x}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiting_fails_over() {
+        assert!(is_endpoint_error(&ProviderError::RateLimited));
+        assert!(is_unsent_request_error(&ProviderError::RateLimited));
+    }
+
+    #[test]
+    fn test_starknet_errors_never_fail_over() {
+        let err = ProviderError::StarknetError(StarknetError::ContractNotFound);
+
+        assert!(!is_endpoint_error(&err));
+        assert!(!is_unsent_request_error(&err));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn test_connection_failures_fail_over() {
+        // Nothing listens on port 1, so the connection is refused before the request is sent
+        let provider = JsonRpcClient::new(HttpTransport::new(
+            Url::parse("http://127.0.0.1:1").unwrap(),
+        ));
+        let err = provider.chain_id().await.unwrap_err();
+
+        assert!(is_endpoint_error(&err));
+        assert!(is_unsent_request_error(&err));
     }
 }
@@ -10,7 +10,11 @@ use starknet::{
     signers::{LocalWallet, Signer, SignerInteractivityContext, SigningKey, VerifyingKey},
 };
 
-use crate::hd_path::{DerivationPathParser, Eip2645Path};
+use crate::{
+    config::ProjectConfig,
+    hd_path::{DerivationPathParser, Eip2645Path},
+    profile::NetworkSigner,
+};
 
 #[cfg(feature = "ledger")]
 type LedgerSigner = starknet::signers::LedgerSigner;
@@ -142,14 +146,14 @@ impl Signer for AnySigner {
 }
 
 impl SignerArgs {
-    pub async fn into_signer(self) -> Result<AnySigner> {
-        self.into_task()?.resolve().await
+    pub async fn into_signer(self, project_config: Option<&ProjectConfig>) -> Result<AnySigner> {
+        self.into_task(project_config)?.resolve().await
     }
 
     /// Parses the options into a resolution task without immediately performing the resolution.
     /// This method allows callers to defer resolution to a later stage while still performing some
     /// initial validations.
-    pub fn into_task(self) -> Result<SignerResolutionTask> {
+    pub fn into_task(self, project_config: Option<&ProjectConfig>) -> Result<SignerResolutionTask> {
         // We're not using the `env` derive from `clap` because we need to distinguish between
         // whether the value is supplied from the command line or the environment variable.
         //
@@ -187,6 +191,15 @@ impl SignerArgs {
             },
         };
 
+        // The keystore set in the project file has the lowest priority, and is only used when no
+        // other signer source is supplied. It's otherwise treated the same as env vars.
+        let keystore = match (keystore, &private_key, &ledger_path) {
+            (None, None, None) => project_config
+                .and_then(|config| config.resolved_keystore())
+                .map(StringValue::FromEnvVar),
+            (keystore, _, _) => keystore,
+        };
+
         let task =
             match (keystore, &keystore_password, private_key, ledger_path) {
                 // Options:
@@ -346,6 +359,21 @@ impl SignerResolutionTask {
     }
 }
 
+impl SignerResolutionTaskContent {
+    /// Creates the resolution task content for a network's default signer.
+    pub fn from_network_signer(signer: &NetworkSigner) -> Result<Self> {
+        Ok(match signer {
+            NetworkSigner::Keystore { path } => Self::Keystore(KeystoreTaskContent {
+                keystore: path.to_owned(),
+                keystore_password: std::env::var("STARKNET_KEYSTORE_PASSWORD").ok(),
+            }),
+            NetworkSigner::Ledger { path } => Self::Ledger(LedgerTaskContent {
+                path: Eip2645Path::from_str(path)?.into(),
+            }),
+        })
+    }
+}
+
 impl KeystoreTaskContent {
     pub fn resolve(self) -> Result<AnySigner> {
         if self.keystore.is_empty() {
@@ -0,0 +1,337 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use num_bigint::BigUint;
+use num_traits::{ToPrimitive, Zero};
+use starknet::{
+    core::types::{BlockId, BlockTag, Felt, FunctionCall},
+    macros::selector,
+    providers::Provider,
+};
+
+use crate::chain_id::OfflineSource;
+
+/// Name of the address book entry holding the Starknet ID naming contract address of a chain.
+/// Built-in entries exist for mainnet and Sepolia, which can be overridden like any other entry.
+pub const NAMING_CONTRACT_ENTRY: &str = "starknet_id_naming";
+
+const DOMAIN_SUFFIX: &str = ".stark";
+
+/// Characters encoded in the base alphabet. Starknet ID also defines an extended alphabet with
+/// non-ASCII characters, which is not supported here.
+const BASIC_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+/// Size of the basic alphabet plus one, which is the base each character is encoded in.
+const BASIC_BASE: u32 = 38;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ContractCaller {
+    async fn call_contract(
+        &self,
+        contract_address: Felt,
+        entry_point_selector: Felt,
+        calldata: Vec<Felt>,
+    ) -> Result<Vec<Felt>>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<T> ContractCaller for T
+where
+    T: Provider + Send + Sync,
+{
+    async fn call_contract(
+        &self,
+        contract_address: Felt,
+        entry_point_selector: Felt,
+        calldata: Vec<Felt>,
+    ) -> Result<Vec<Felt>> {
+        self.call(
+            FunctionCall {
+                contract_address,
+                entry_point_selector,
+                calldata,
+            },
+            BlockId::Tag(BlockTag::Pending),
+        )
+        .await
+        .map_err(|err| anyhow::anyhow!("unable to call contract: {err}"))
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ContractCaller for OfflineSource {
+    async fn call_contract(
+        &self,
+        _contract_address: Felt,
+        _entry_point_selector: Felt,
+        _calldata: Vec<Felt>,
+    ) -> Result<Vec<Felt>> {
+        Err(anyhow::anyhow!(
+            "network access is not available in local commands, so Starknet ID domains cannot \
+            be resolved"
+        ))
+    }
+}
+
+/// Resolves a `.stark` domain into an address with the naming contract. The `.stark` suffix is
+/// optional.
+pub async fn domain_to_address<C>(caller: &C, naming_contract: Felt, domain: &str) -> Result<Felt>
+where
+    C: ContractCaller,
+{
+    let encoded = encode_domain(domain)?;
+
+    let mut calldata = vec![Felt::from(encoded.len())];
+    calldata.extend(encoded);
+    // Empty hint
+    calldata.push(Felt::ZERO);
+
+    let result = caller
+        .call_contract(naming_contract, selector!("domain_to_address"), calldata)
+        .await?;
+
+    match result.first() {
+        Some(address) if *address != Felt::ZERO => Ok(*address),
+        Some(_) => Err(anyhow::anyhow!(
+            "Starknet ID domain \"{}\" is not registered or not pointing to any address",
+            domain
+        )),
+        None => Err(anyhow::anyhow!(
+            "unexpected empty response from the naming contract"
+        )),
+    }
+}
+
+/// Looks up the main `.stark` domain of an address with the naming contract, if any.
+pub async fn address_to_domain<C>(
+    caller: &C,
+    naming_contract: Felt,
+    address: Felt,
+) -> Result<Option<String>>
+where
+    C: ContractCaller,
+{
+    let result = caller
+        .call_contract(
+            naming_contract,
+            selector!("address_to_domain"),
+            // Empty hint
+            vec![address, Felt::ZERO],
+        )
+        .await?;
+
+    // The response is a span of encoded labels, with the length prefix
+    let (len, labels) = result
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("unexpected empty response from the naming contract"))?;
+    if *len != Felt::from(labels.len()) {
+        anyhow::bail!("unexpected response length from the naming contract");
+    }
+
+    if labels.is_empty() {
+        return Ok(None);
+    }
+
+    let labels = labels
+        .iter()
+        .map(decode_label)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(format!("{}{}", labels.join("."), DOMAIN_SUFFIX)))
+}
+
+/// Encodes each label of a domain, from the leftmost subdomain to the root.
+fn encode_domain(domain: &str) -> Result<Vec<Felt>> {
+    let domain = domain.strip_suffix(DOMAIN_SUFFIX).unwrap_or(domain);
+    if domain.is_empty() {
+        anyhow::bail!("empty Starknet ID domain");
+    }
+
+    domain
+        .split('.')
+        .map(|label| {
+            encode_label(label).map_err(|err| {
+                anyhow::anyhow!("invalid Starknet ID domain \"{}\": {}", domain, err)
+            })
+        })
+        .collect()
+}
+
+fn encode_label(label: &str) -> Result<Felt> {
+    if label.is_empty() {
+        anyhow::bail!("empty label");
+    }
+
+    let mut encoded = BigUint::zero();
+    let mut multiplier = BigUint::from(1u32);
+
+    for (ind, char) in label.bytes().enumerate() {
+        let index = BASIC_ALPHABET
+            .iter()
+            .position(|c| *c == char)
+            .ok_or_else(|| anyhow::anyhow!("unsupported character '{}'", char as char))?;
+
+        if ind == label.len() - 1 && index == 0 {
+            // A trailing `a` would otherwise be encoded as zero and lost, so it's encoded as an
+            // escape code followed by an extended alphabet code of zero
+            encoded += &multiplier * (BASIC_ALPHABET.len() as u32);
+            multiplier *= BASIC_BASE * BASIC_BASE;
+        } else {
+            encoded += &multiplier * (index as u32);
+            multiplier *= BASIC_BASE;
+        }
+    }
+
+    if encoded.bits() > 251 {
+        anyhow::bail!("label \"{}\" too long", label);
+    }
+
+    Ok(Felt::from_bytes_be_slice(&encoded.to_bytes_be()))
+}
+
+fn decode_label(encoded: &Felt) -> Result<String> {
+    let mut remaining = BigUint::from_bytes_be(&encoded.to_bytes_be());
+    let mut decoded = String::new();
+
+    while !remaining.is_zero() {
+        // Always in range as the remainder is less than the base
+        let code = (&remaining % BASIC_BASE).to_usize().unwrap();
+        remaining /= BASIC_BASE;
+
+        if code < BASIC_ALPHABET.len() {
+            decoded.push(BASIC_ALPHABET[code] as char);
+        } else {
+            // Escape code for the extended alphabet. Only a trailing `a`, which is escaped with an
+            // extended code of zero, is supported.
+            if !remaining.is_zero() {
+                anyhow::bail!("domain contains unsupported characters");
+            }
+
+            decoded.push(BASIC_ALPHABET[0] as char);
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::macros::felt;
+
+    use super::*;
+
+    const NAMING_CONTRACT: Felt = felt!("0x1234");
+
+    /// Checks the request against the expected one and responds with a fixed result.
+    struct StubCaller {
+        calldata: Vec<Felt>,
+        result: Vec<Felt>,
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    impl ContractCaller for StubCaller {
+        async fn call_contract(
+            &self,
+            contract_address: Felt,
+            entry_point_selector: Felt,
+            calldata: Vec<Felt>,
+        ) -> Result<Vec<Felt>> {
+            assert_eq!(contract_address, NAMING_CONTRACT);
+            assert_eq!(entry_point_selector, selector!("domain_to_address"));
+            assert_eq!(calldata, self.calldata);
+
+            Ok(self.result.clone())
+        }
+    }
+
+    #[test]
+    fn test_encode_label() {
+        // Reference values from the starknetid.js encoding
+        for (label, encoded) in [
+            ("ben", Felt::from(18925u64)),
+            ("fricoben", Felt::from(1499554868251u64)),
+            ("aaaa", Felt::from(2030264u64)),
+            ("ba", Felt::from(1407u64)),
+            ("a", Felt::from(37u64)),
+        ] {
+            assert_eq!(encode_label(label).unwrap(), encoded, "{}", label);
+        }
+    }
+
+    #[test]
+    fn test_encode_label_invalid() {
+        assert!(encode_label("").is_err());
+        assert!(encode_label("Ben").is_err());
+        assert!(encode_label("ben_").is_err());
+        assert!(encode_label(&"z".repeat(49)).is_err());
+    }
+
+    #[test]
+    fn test_encode_domain() {
+        assert_eq!(
+            encode_domain("ben.stark").unwrap(),
+            vec![Felt::from(18925u64)]
+        );
+        assert_eq!(encode_domain("ben").unwrap(), vec![Felt::from(18925u64)]);
+        assert_eq!(
+            encode_domain("aaaa.ben.stark").unwrap(),
+            vec![Felt::from(2030264u64), Felt::from(18925u64)]
+        );
+
+        assert!(encode_domain(".stark").is_err());
+        assert!(encode_domain("ben..stark").is_err());
+    }
+
+    #[test]
+    fn test_label_round_trip() {
+        for label in [
+            "a",
+            "ben",
+            "aaaa",
+            "ba",
+            "starkli",
+            "abc-123",
+            "0a",
+            "zzzzzzzzzz",
+            "-",
+        ] {
+            let encoded = encode_label(label).unwrap();
+            assert_eq!(decode_label(&encoded).unwrap(), label);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_domain_to_address() {
+        let caller = StubCaller {
+            calldata: vec![
+                Felt::from(2u64),
+                Felt::from(2030264u64),
+                Felt::from(18925u64),
+                Felt::ZERO,
+            ],
+            result: vec![felt!("0x5678")],
+        };
+
+        assert_eq!(
+            domain_to_address(&caller, NAMING_CONTRACT, "aaaa.ben.stark")
+                .await
+                .unwrap(),
+            felt!("0x5678")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_domain_to_address_unregistered() {
+        let caller = StubCaller {
+            calldata: vec![Felt::from(1u64), Felt::from(18925u64), Felt::ZERO],
+            result: vec![Felt::ZERO],
+        };
+
+        assert!(domain_to_address(&caller, NAMING_CONTRACT, "ben.stark")
+            .await
+            .is_err());
+    }
+}
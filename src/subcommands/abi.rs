@@ -1,16 +1,48 @@
-use std::path::PathBuf;
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{builder::PossibleValue, Parser, ValueEnum};
 use serde_json_pythonic::to_string_pythonic;
-use starknet::core::types::contract::{legacy::LegacyContractClass, CompiledClass, SierraClass};
+use starknet::{
+    core::types::{
+        contract::{
+            legacy::{LegacyContractClass, RawLegacyAbiEntry, RawLegacyMember},
+            AbiEntry, AbiEvent, AbiFunction, AbiNamedMember, CompiledClass, EventField,
+            EventFieldKind, SierraClass, StateMutability, TypedAbiEvent,
+        },
+        BlockId, BlockTag, ContractClass,
+    },
+    providers::Provider,
+};
 
-use crate::{path::ExpandedPathbufParser, utils::print_colored_json};
+use crate::{
+    address_book::AddressBookResolver,
+    decode::FeltDecoder,
+    error::provider_error_mapper,
+    path::ExpandedPathbufParser,
+    utils::{parse_compressed_legacy_class, parse_flattened_sierra_class, print_colored_json},
+    verbosity::VerbosityArgs,
+    ProviderArgs,
+};
 
 #[derive(Debug, Parser)]
 pub struct Abi {
+    #[clap(flatten)]
+    provider: ProviderArgs,
     #[clap(
         long,
+        value_enum,
+        default_value_t = AbiFormat::Json,
+        help = "Output format. `interface` renders the ABI as Cairo-like signatures"
+    )]
+    format: AbiFormat,
+    #[clap(
+        long,
+        conflicts_with = "format",
         help = "Present the ABI as a flattened string in a Pythoic style"
     )]
     flatten: bool,
@@ -19,56 +51,620 @@ pub struct Abi {
         help = "When --flatten is used, serialize the ABI in the Pythoic style instead of compact"
     )]
     pythonic: bool,
+    #[clap(
+        long,
+        conflicts_with_all = ["file", "class_hash"],
+        help = "Fetch the ABI of the class deployed at this address instead of reading a file"
+    )]
+    address: Option<String>,
+    #[clap(
+        long,
+        conflicts_with = "file",
+        help = "Fetch the ABI of the declared class with this hash instead of reading a file"
+    )]
+    class_hash: Option<String>,
     #[clap(
         value_parser = ExpandedPathbufParser,
+        required_unless_present_any = ["address", "class_hash"],
         help = "Path to contract artifact file"
     )]
-    file: PathBuf,
+    file: Option<PathBuf>,
+    #[clap(flatten)]
+    verbosity: VerbosityArgs,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AbiFormat {
+    Json,
+    Interface,
+}
+
+enum ClassAbi {
+    Sierra(Vec<AbiEntry>),
+    Legacy(Vec<RawLegacyAbiEntry>),
 }
 
 impl Abi {
-    pub fn run(self) -> Result<()> {
+    pub async fn run(self) -> Result<()> {
+        self.verbosity.setup_logging();
+
         if self.pythonic && !self.flatten {
             anyhow::bail!("--pythonic can only be used with --flatten");
         }
 
+        let abi = match &self.file {
+            Some(file) => Self::load_from_file(file)?,
+            None => self.fetch().await?,
+        };
+
+        match (abi, self.format) {
+            (ClassAbi::Sierra(abi), AbiFormat::Interface) => {
+                print!("{}", render_sierra_interface(&abi))
+            }
+            (ClassAbi::Legacy(abi), AbiFormat::Interface) => {
+                print!("{}", render_legacy_interface(&abi))
+            }
+            (ClassAbi::Sierra(abi), AbiFormat::Json) => self.print_json(&abi)?,
+            (ClassAbi::Legacy(abi), AbiFormat::Json) => self.print_json(&abi)?,
+        }
+
+        Ok(())
+    }
+
+    fn load_from_file(file: &Path) -> Result<ClassAbi> {
         // Working around a deserialization bug in `starknet-rs`:
         //   https://github.com/xJonathanLEI/starknet-rs/issues/392
 
-        if let Ok(class) =
-            serde_json::from_reader::<_, SierraClass>(std::fs::File::open(&self.file)?)
-        {
-            let abi = class.abi;
-            if self.flatten {
-                if self.pythonic {
-                    println!("{}", to_string_pythonic(&abi)?);
-                } else {
-                    println!("{}", serde_json::to_string(&abi)?);
-                }
-            } else {
-                print_colored_json(&abi)?;
-            }
+        if let Ok(class) = serde_json::from_reader::<_, SierraClass>(std::fs::File::open(file)?) {
+            Ok(ClassAbi::Sierra(class.abi))
         } else if let Ok(class) =
-            serde_json::from_reader::<_, LegacyContractClass>(std::fs::File::open(&self.file)?)
-        {
-            let abi = class.abi;
-            if self.flatten {
-                if self.pythonic {
-                    println!("{}", to_string_pythonic(&abi)?);
-                } else {
-                    println!("{}", serde_json::to_string(&abi)?);
-                }
-            } else {
-                print_colored_json(&abi)?;
-            }
-        } else if serde_json::from_reader::<_, CompiledClass>(std::fs::File::open(self.file)?)
-            .is_ok()
+            serde_json::from_reader::<_, LegacyContractClass>(std::fs::File::open(file)?)
         {
+            Ok(ClassAbi::Legacy(class.abi))
+        } else if serde_json::from_reader::<_, CompiledClass>(std::fs::File::open(file)?).is_ok() {
             anyhow::bail!("cannot extract ABI from casm");
         } else {
             anyhow::bail!("failed to parse contract artifact");
         }
+    }
+
+    async fn fetch(&self) -> Result<ClassAbi> {
+        let provider = Arc::new(self.provider.clone().into_provider()?);
+        let block_id = BlockId::Tag(BlockTag::Pending);
+
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+
+        let class = match (&self.address, &self.class_hash) {
+            (Some(address), _) => {
+                let address = felt_decoder
+                    .decode_single_with_addr_fallback(address)
+                    .await?;
+
+                provider.get_class_at(block_id, address).await
+            }
+            (None, Some(class_hash)) => {
+                let class_hash = felt_decoder.decode_single(class_hash).await?;
+
+                provider.get_class(block_id, class_hash).await
+            }
+            // Enforced by clap
+            (None, None) => unreachable!(),
+        }
+        .map_err(provider_error_mapper)?;
+
+        Ok(match class {
+            ContractClass::Sierra(class) => {
+                ClassAbi::Sierra(parse_flattened_sierra_class(class)?.abi)
+            }
+            ContractClass::Legacy(class) => {
+                ClassAbi::Legacy(parse_compressed_legacy_class(class)?.abi)
+            }
+        })
+    }
+
+    fn print_json<T>(&self, abi: &T) -> Result<()>
+    where
+        T: serde::Serialize,
+    {
+        if self.flatten {
+            if self.pythonic {
+                println!("{}", to_string_pythonic(abi)?);
+            } else {
+                println!("{}", serde_json::to_string(abi)?);
+            }
+        } else {
+            print_colored_json(abi)?;
+        }
 
         Ok(())
     }
 }
+
+impl ValueEnum for AbiFormat {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Json, Self::Interface]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            Self::Json => Some(PossibleValue::new("json")),
+            Self::Interface => Some(PossibleValue::new("interface")),
+        }
+    }
+}
+
+/// Renders a Sierra ABI in Cairo syntax. Functions are grouped under the impls exposing them, or
+/// the interfaces declaring them if no impl is found.
+fn render_sierra_interface(entries: &[AbiEntry]) -> String {
+    let mut blocks = vec![];
+
+    let impl_interfaces = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            AbiEntry::Impl(abi_impl) => Some(abi_impl.interface_name.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for entry in entries.iter() {
+        let mut block = String::new();
+
+        match entry {
+            AbiEntry::Impl(abi_impl) => {
+                let interface = entries.iter().find_map(|entry| match entry {
+                    AbiEntry::Interface(interface) if interface.name == abi_impl.interface_name => {
+                        Some(interface)
+                    }
+                    _ => None,
+                });
+
+                writeln!(block, "#[abi(embed_v0)]").unwrap();
+                writeln!(
+                    block,
+                    "impl {} of {} {{",
+                    abi_impl.name,
+                    display_type(&abi_impl.interface_name)
+                )
+                .unwrap();
+                if let Some(interface) = interface {
+                    for item in interface.items.iter() {
+                        if let AbiEntry::Function(function) = item {
+                            writeln!(block, "    {};", render_function(function, "ContractState"))
+                                .unwrap();
+                        }
+                    }
+                }
+                writeln!(block, "}}").unwrap();
+            }
+            AbiEntry::Interface(interface) => {
+                // Already rendered with the impl
+                if impl_interfaces.contains(&interface.name.as_str()) {
+                    continue;
+                }
+
+                writeln!(block, "#[starknet::interface]").unwrap();
+                writeln!(
+                    block,
+                    "trait {}<TContractState> {{",
+                    display_type(&interface.name)
+                )
+                .unwrap();
+                for item in interface.items.iter() {
+                    if let AbiEntry::Function(function) = item {
+                        writeln!(
+                            block,
+                            "    {};",
+                            render_function(function, "TContractState")
+                        )
+                        .unwrap();
+                    }
+                }
+                writeln!(block, "}}").unwrap();
+            }
+            AbiEntry::Function(function) => {
+                match function.state_mutability {
+                    StateMutability::View => writeln!(block, "#[view]").unwrap(),
+                    StateMutability::External => writeln!(block, "#[external(v0)]").unwrap(),
+                }
+                writeln!(block, "{};", render_function(function, "ContractState")).unwrap();
+            }
+            AbiEntry::L1Handler(function) => {
+                writeln!(block, "#[l1_handler]").unwrap();
+                writeln!(block, "{};", render_function(function, "ContractState")).unwrap();
+            }
+            AbiEntry::Constructor(constructor) => {
+                writeln!(block, "#[constructor]").unwrap();
+                writeln!(
+                    block,
+                    "fn {}(ref self: ContractState{});",
+                    constructor.name,
+                    render_params(&constructor.inputs, true)
+                )
+                .unwrap();
+            }
+            AbiEntry::Struct(abi_struct) => {
+                writeln!(block, "struct {} {{", display_type(&abi_struct.name)).unwrap();
+                for member in abi_struct.members.iter() {
+                    writeln!(
+                        block,
+                        "    {}: {},",
+                        member.name,
+                        display_type(&member.r#type)
+                    )
+                    .unwrap();
+                }
+                writeln!(block, "}}").unwrap();
+            }
+            AbiEntry::Enum(abi_enum) => {
+                writeln!(block, "enum {} {{", display_type(&abi_enum.name)).unwrap();
+                for variant in abi_enum.variants.iter() {
+                    if variant.r#type == "()" {
+                        writeln!(block, "    {},", variant.name).unwrap();
+                    } else {
+                        writeln!(
+                            block,
+                            "    {}: {},",
+                            variant.name,
+                            display_type(&variant.r#type)
+                        )
+                        .unwrap();
+                    }
+                }
+                writeln!(block, "}}").unwrap();
+            }
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(event))) => {
+                writeln!(block, "#[derive(starknet::Event)]").unwrap();
+                writeln!(block, "struct {} {{", display_type(&event.name)).unwrap();
+                render_event_fields(&mut block, &event.members);
+                writeln!(block, "}}").unwrap();
+            }
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(event))) => {
+                writeln!(block, "#[event]").unwrap();
+                writeln!(block, "#[derive(starknet::Event)]").unwrap();
+                writeln!(block, "enum {} {{", display_type(&event.name)).unwrap();
+                render_event_fields(&mut block, &event.variants);
+                writeln!(block, "}}").unwrap();
+            }
+            AbiEntry::Event(AbiEvent::Untyped(event)) => {
+                writeln!(block, "#[event]").unwrap();
+                writeln!(
+                    block,
+                    "fn {}({});",
+                    event.name,
+                    render_params(&event.inputs, false)
+                )
+                .unwrap();
+            }
+        }
+
+        blocks.push(block);
+    }
+
+    blocks.join("\n")
+}
+
+fn render_function(function: &AbiFunction, state_type: &str) -> String {
+    let self_param = match function.state_mutability {
+        StateMutability::View => format!("self: @{state_type}"),
+        StateMutability::External => format!("ref self: {state_type}"),
+    };
+
+    let outputs = function
+        .outputs
+        .iter()
+        .map(|output| display_type(&output.r#type))
+        .collect::<Vec<_>>();
+    let outputs = match outputs.len() {
+        0 => String::new(),
+        1 => format!(" -> {}", outputs[0]),
+        _ => format!(" -> ({})", outputs.join(", ")),
+    };
+
+    format!(
+        "fn {}({}{}){}",
+        function.name,
+        self_param,
+        render_params(&function.inputs, true),
+        outputs
+    )
+}
+
+/// Renders parameters as `name: Type` pairs, with a leading separator when following `self`.
+fn render_params(params: &[AbiNamedMember], after_self: bool) -> String {
+    let params = params
+        .iter()
+        .map(|param| format!("{}: {}", param.name, display_type(&param.r#type)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if after_self && !params.is_empty() {
+        format!(", {params}")
+    } else {
+        params
+    }
+}
+
+fn render_event_fields(block: &mut String, fields: &[EventField]) {
+    for field in fields.iter() {
+        match field.kind {
+            EventFieldKind::Key => writeln!(block, "    #[key]").unwrap(),
+            EventFieldKind::Nested => {}
+            EventFieldKind::Flat => writeln!(block, "    #[flat]").unwrap(),
+            EventFieldKind::Data => {}
+        }
+        writeln!(
+            block,
+            "    {}: {},",
+            field.name,
+            display_type(&field.r#type)
+        )
+        .unwrap();
+    }
+}
+
+/// Renders a legacy ABI in Cairo 0 syntax.
+fn render_legacy_interface(entries: &[RawLegacyAbiEntry]) -> String {
+    let mut blocks = vec![];
+
+    for entry in entries.iter() {
+        let mut block = String::new();
+
+        match entry {
+            RawLegacyAbiEntry::Constructor(constructor) => {
+                writeln!(block, "@constructor").unwrap();
+                writeln!(
+                    block,
+                    "func {}({});",
+                    constructor.name,
+                    render_legacy_members(&constructor.inputs)
+                )
+                .unwrap();
+            }
+            RawLegacyAbiEntry::Function(function) => {
+                match function.state_mutability.as_deref() {
+                    Some(state_mutability) => writeln!(block, "@{state_mutability}").unwrap(),
+                    None => writeln!(block, "@external").unwrap(),
+                }
+                write!(
+                    block,
+                    "func {}({})",
+                    function.name,
+                    render_legacy_members(&function.inputs)
+                )
+                .unwrap();
+                if !function.outputs.is_empty() {
+                    write!(block, " -> ({})", render_legacy_members(&function.outputs)).unwrap();
+                }
+                writeln!(block, ";").unwrap();
+            }
+            RawLegacyAbiEntry::L1Handler(handler) => {
+                writeln!(block, "@l1_handler").unwrap();
+                writeln!(
+                    block,
+                    "func {}({});",
+                    handler.name,
+                    render_legacy_members(&handler.inputs)
+                )
+                .unwrap();
+            }
+            RawLegacyAbiEntry::Struct(legacy_struct) => {
+                writeln!(block, "struct {} {{", legacy_struct.name).unwrap();
+                for member in legacy_struct.members.iter() {
+                    writeln!(block, "    {}: {},", member.name, member.r#type).unwrap();
+                }
+                writeln!(block, "}}").unwrap();
+            }
+            RawLegacyAbiEntry::Event(event) => {
+                writeln!(block, "@event").unwrap();
+                if !event.keys.is_empty() {
+                    writeln!(block, "// keys: {}", render_legacy_members(&event.keys)).unwrap();
+                }
+                writeln!(
+                    block,
+                    "func {}({});",
+                    event.name,
+                    render_legacy_members(&event.data)
+                )
+                .unwrap();
+            }
+        }
+
+        blocks.push(block);
+    }
+
+    blocks.join("\n")
+}
+
+fn render_legacy_members(members: &[RawLegacyMember]) -> String {
+    members
+        .iter()
+        .map(|member| format!("{}: {}", member.name, member.r#type))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Shortens paths of core library types for readability, such as turning
+/// `core::array::Span::<core::felt252>` into `Span<felt252>`. Other paths are left untouched, as
+/// user-defined types can share the same names.
+fn display_type(ty: &str) -> String {
+    let mut output = String::new();
+    let mut start = 0;
+
+    for (ind, c) in ty.char_indices() {
+        if matches!(c, '<' | '>' | '(' | ')' | '[' | ']' | ',' | ' ' | '@') {
+            output.push_str(&shorten_path(&ty[start..ind]));
+            output.push(c);
+            start = ind + c.len_utf8();
+        }
+    }
+    output.push_str(&shorten_path(&ty[start..]));
+
+    output
+}
+
+fn shorten_path(path: &str) -> &str {
+    // Turbofish generics like `Span::<T>`
+    let path = path.strip_suffix("::").unwrap_or(path);
+
+    if path.starts_with("core::") {
+        path.rsplit("::").next().unwrap_or(path)
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_type() {
+        assert_eq!(
+            display_type("core::array::Span::<core::felt252>"),
+            "Span<felt252>"
+        );
+        assert_eq!(
+            display_type("(core::integer::u8, core::bool, core::integer::u256)"),
+            "(u8, bool, u256)"
+        );
+        assert_eq!(
+            display_type(
+                "@core::array::Array::<core::starknet::contract_address::ContractAddress>"
+            ),
+            "@Array<ContractAddress>"
+        );
+        assert_eq!(
+            display_type("my_contract::Span::<core::felt252>"),
+            "my_contract::Span<felt252>"
+        );
+    }
+
+    #[test]
+    fn test_shorten_path() {
+        assert_eq!(shorten_path("core::felt252"), "felt252");
+        assert_eq!(shorten_path("core::array::Span::"), "Span");
+        assert_eq!(shorten_path("my_contract::Pair"), "my_contract::Pair");
+        assert_eq!(shorten_path(""), "");
+    }
+
+    #[test]
+    fn test_render_sierra_interface() {
+        let abi: Vec<AbiEntry> = serde_json::from_str(
+            r#"[
+                {
+                    "type": "impl",
+                    "name": "CounterImpl",
+                    "interface_name": "counter::ICounter"
+                },
+                {
+                    "type": "interface",
+                    "name": "counter::ICounter",
+                    "items": [
+                        {
+                            "type": "function",
+                            "name": "get",
+                            "inputs": [],
+                            "outputs": [{ "type": "core::felt252" }],
+                            "state_mutability": "view"
+                        },
+                        {
+                            "type": "function",
+                            "name": "set",
+                            "inputs": [{ "name": "value", "type": "core::felt252" }],
+                            "outputs": [],
+                            "state_mutability": "external"
+                        }
+                    ]
+                },
+                {
+                    "type": "constructor",
+                    "name": "constructor",
+                    "inputs": [
+                        {
+                            "name": "owner",
+                            "type": "core::starknet::contract_address::ContractAddress"
+                        }
+                    ]
+                },
+                {
+                    "type": "function",
+                    "name": "owner",
+                    "inputs": [],
+                    "outputs": [
+                        { "type": "core::starknet::contract_address::ContractAddress" }
+                    ],
+                    "state_mutability": "view"
+                },
+                {
+                    "type": "function",
+                    "name": "reset",
+                    "inputs": [],
+                    "outputs": [],
+                    "state_mutability": "external"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            render_sierra_interface(&abi),
+            "#[abi(embed_v0)]\n\
+            impl CounterImpl of counter::ICounter {\n    \
+                fn get(self: @ContractState) -> felt252;\n    \
+                fn set(ref self: ContractState, value: felt252);\n\
+            }\n\
+            \n\
+            #[constructor]\n\
+            fn constructor(ref self: ContractState, owner: ContractAddress);\n\
+            \n\
+            #[view]\n\
+            fn owner(self: @ContractState) -> ContractAddress;\n\
+            \n\
+            #[external(v0)]\n\
+            fn reset(ref self: ContractState);\n"
+        );
+    }
+
+    #[test]
+    fn test_render_legacy_interface() {
+        let abi: Vec<RawLegacyAbiEntry> = serde_json::from_str(
+            r#"[
+                {
+                    "type": "constructor",
+                    "name": "constructor",
+                    "inputs": [{ "name": "owner", "type": "felt" }],
+                    "outputs": []
+                },
+                {
+                    "type": "function",
+                    "name": "balanceOf",
+                    "inputs": [{ "name": "account", "type": "felt" }],
+                    "outputs": [{ "name": "balance", "type": "Uint256" }],
+                    "stateMutability": "view"
+                },
+                {
+                    "type": "function",
+                    "name": "transfer",
+                    "inputs": [
+                        { "name": "recipient", "type": "felt" },
+                        { "name": "amount", "type": "Uint256" }
+                    ],
+                    "outputs": []
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            render_legacy_interface(&abi),
+            "@constructor\n\
+            func constructor(owner: felt);\n\
+            \n\
+            @view\n\
+            func balanceOf(account: felt) -> (balance: Uint256);\n\
+            \n\
+            @external\n\
+            func transfer(recipient: felt, amount: Uint256);\n"
+        );
+    }
+}
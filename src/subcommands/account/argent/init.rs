@@ -13,6 +13,7 @@ use crate::{
     account::{
         AccountConfig, AccountVariant, ArgentAccountConfig, DeploymentStatus, UndeployedStatus,
     },
+    config::ProjectConfig,
     path::ExpandedPathbufParser,
     signer::SignerArgs,
 };
@@ -45,7 +46,7 @@ impl Init {
             anyhow::bail!("account config file already exists");
         }
 
-        let signer = self.signer.into_signer().await?;
+        let signer = self.signer.into_signer(ProjectConfig::load()?).await?;
 
         // Too lazy to write random salt generation
         let salt = SigningKey::from_random().secret_scalar();
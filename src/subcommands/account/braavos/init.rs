@@ -15,6 +15,7 @@ use crate::{
         BraavosMultisigConfig, BraavosSigner, BraavosStarkSigner, DeploymentContext,
         DeploymentStatus, UndeployedStatus,
     },
+    config::ProjectConfig,
     path::ExpandedPathbufParser,
     signer::SignerArgs,
 };
@@ -49,7 +50,7 @@ impl Init {
             anyhow::bail!("account config file already exists");
         }
 
-        let signer = self.signer.into_signer().await?;
+        let signer = self.signer.into_signer(ProjectConfig::load()?).await?;
 
         // Too lazy to write random salt generation
         let salt = SigningKey::from_random().secret_scalar();
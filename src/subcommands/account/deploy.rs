@@ -1,4 +1,4 @@
-use std::{io::Write, path::PathBuf, sync::Arc, time::Duration};
+use std::{io::Write, path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
@@ -18,6 +18,7 @@ use crate::{
         DeploymentContext, DeploymentStatus,
     },
     account_factory::{AnyAccountFactory, BraavosAccountFactory},
+    config::resolve_poll_interval,
     error::account_factory_error_mapper,
     fee::{FeeArgs, FeeSetting, FeeToken, TokenFeeSetting},
     path::ExpandedPathbufParser,
@@ -43,10 +44,9 @@ pub struct Deploy {
     #[clap(
         long,
         env = "STARKNET_POLL_INTERVAL",
-        default_value = "5000",
-        help = "Transaction result poll interval in milliseconds"
+        help = "Transaction result poll interval in milliseconds [default: 5000]"
     )]
-    poll_interval: u64,
+    poll_interval: Option<u64>,
     #[clap(
         value_parser = ExpandedPathbufParser,
         help = "Path to the account config file"
@@ -76,13 +76,14 @@ impl Deploy {
     pub async fn run(self) -> Result<()> {
         self.verbosity.setup_logging();
 
-        let fee_setting = self.fee.into_setting()?;
+        let provider = Arc::new(self.provider.into_provider()?);
+
+        let fee_setting = self.fee.into_setting(provider.project_config())?;
         if self.simulate && fee_setting.is_estimate_only() {
             anyhow::bail!("--simulate cannot be used with --estimate-only");
         }
 
-        let provider = Arc::new(self.provider.into_provider()?);
-        let signer = Arc::new(self.signer.into_signer().await?);
+        let signer = Arc::new(self.signer.into_signer(provider.project_config()).await?);
 
         if !self.file.exists() {
             anyhow::bail!("account config file not found");
@@ -360,6 +361,8 @@ impl Deploy {
 
                 fee_prompt(fee_type, target_deployment_address, FeeToken::Strk)?;
 
+                provider.verify_chain_id().await?;
+
                 account_deployment.send().await
             }
         }
@@ -382,7 +385,7 @@ impl Deploy {
         watch_tx(
             &provider,
             account_deployment_tx,
-            Duration::from_millis(self.poll_interval),
+            resolve_poll_interval(self.poll_interval, provider.project_config()),
         )
         .await?;
 
@@ -11,6 +11,7 @@ use starknet::{
 
 use crate::{
     account::{AccountConfig, AccountVariant, DeploymentStatus, OzAccountConfig, UndeployedStatus},
+    config::ProjectConfig,
     path::ExpandedPathbufParser,
     signer::SignerArgs,
 };
@@ -60,7 +61,7 @@ impl Init {
             None => OZ_ACCOUNT_CLASS_HASH,
         };
 
-        let signer = self.signer.into_signer().await?;
+        let signer = self.signer.into_signer(ProjectConfig::load()?).await?;
 
         // Too lazy to write random salt generation
         let salt = SigningKey::from_random().secret_scalar();
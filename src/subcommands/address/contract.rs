@@ -0,0 +1,39 @@
+use anyhow::Result;
+use clap::Parser;
+use starknet::core::{types::Felt, utils::get_contract_address};
+
+use crate::{address_book::AddressBookResolver, chain_id::OfflineSource, decode::FeltDecoder};
+
+#[derive(Debug, Parser)]
+pub struct Contract {
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Address of the deployer, which is zero for `DEPLOY_ACCOUNT` transactions"
+    )]
+    deployer_address: Felt,
+    #[clap(long, help = "Salt used for the deployment")]
+    salt: Felt,
+    #[clap(help = "Class hash")]
+    class_hash: Felt,
+    #[clap(help = "Raw constructor calldata")]
+    calldata: Vec<String>,
+}
+
+impl Contract {
+    pub async fn run(self) -> Result<()> {
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(OfflineSource));
+
+        let mut calldata = vec![];
+        for element in self.calldata.iter() {
+            calldata.append(&mut felt_decoder.decode(element).await?);
+        }
+
+        let address =
+            get_contract_address(self.salt, self.class_hash, &calldata, self.deployer_address);
+
+        println!("{address:#064x}");
+
+        Ok(())
+    }
+}
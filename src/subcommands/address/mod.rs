@@ -0,0 +1,31 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod udc;
+use udc::Udc;
+
+mod contract;
+use contract::Contract;
+
+#[derive(Debug, Parser)]
+pub struct Address {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    #[clap(about = "Compute the address of a contract deployed through the UDC")]
+    Udc(Udc),
+    #[clap(about = "Compute a contract address from a deployer address, salt, and calldata")]
+    Contract(Contract),
+}
+
+impl Address {
+    pub async fn run(self) -> Result<()> {
+        match self.command {
+            Subcommands::Udc(cmd) => cmd.run().await,
+            Subcommands::Contract(cmd) => cmd.run().await,
+        }
+    }
+}
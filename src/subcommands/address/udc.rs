@@ -0,0 +1,159 @@
+use anyhow::Result;
+use clap::Parser;
+use starknet::core::{
+    types::Felt,
+    utils::{get_udc_deployed_address, UdcUniqueSettings, UdcUniqueness},
+};
+
+use crate::{
+    address_book::AddressBookResolver, chain_id::OfflineSource, decode::FeltDecoder,
+    utils::DEFAULT_UDC_ADDRESS,
+};
+
+#[derive(Debug, Parser)]
+pub struct Udc {
+    #[clap(long, help = "Do not derive contract address from deployer address")]
+    not_unique: bool,
+    #[clap(
+        long,
+        help = "Deployer address. Needed if and only if not using --not-unique. Schemes that need \
+        network access, such as addr, are not supported"
+    )]
+    deployer_address: Option<String>,
+    #[clap(
+        long,
+        help = "UDC address, which only matters when not using --not-unique [default: \
+        0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf]"
+    )]
+    udc_address: Option<Felt>,
+    #[clap(long, help = "Salt used for the deployment")]
+    salt: Felt,
+    #[clap(help = "Class hash")]
+    class_hash: Felt,
+    #[clap(help = "Constructor arguments. Schemes that need network access are not supported")]
+    ctor_args: Vec<String>,
+}
+
+impl Udc {
+    pub async fn run(self) -> Result<()> {
+        let deployed_address = self.deployed_address().await?;
+
+        println!("{deployed_address:#064x}");
+
+        Ok(())
+    }
+
+    async fn deployed_address(self) -> Result<Felt> {
+        // The command works offline, so names can't be resolved
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(OfflineSource));
+
+        let uniqueness = match (self.not_unique, self.deployer_address) {
+            (true, Some(_)) => {
+                anyhow::bail!("--deployer-address must not be used when --not-unique is on");
+            }
+            (false, None) => {
+                anyhow::bail!("--deployer-address must be used when --not-unique is off");
+            }
+            (true, None) => UdcUniqueness::NotUnique,
+            (false, Some(deployer_address)) => UdcUniqueness::Unique(UdcUniqueSettings {
+                deployer_address: felt_decoder.decode_single(&deployer_address).await?,
+                udc_contract_address: self.udc_address.unwrap_or(DEFAULT_UDC_ADDRESS),
+            }),
+        };
+
+        let mut ctor_args = vec![];
+        for element in self.ctor_args.iter() {
+            ctor_args.append(&mut felt_decoder.decode(element).await?);
+        }
+
+        Ok(get_udc_deployed_address(
+            self.salt,
+            self.class_hash,
+            &uniqueness,
+            &ctor_args,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use starknet::{
+        core::{crypto::pedersen_hash, utils::get_contract_address},
+        macros::felt,
+    };
+
+    use super::*;
+
+    const SALT: &str = "0x0018a7a329d1d85b621350f2b5fc9c64b2e57dfe708525f0aff2c90de1e5b9c8";
+    const CLASS_HASH: &str = "0x0750cd490a7cd1572411169eaa8be292325990d33c5d4733655fe6b926985062";
+
+    async fn deployed_address(args: &[&str]) -> Result<Felt> {
+        Udc::try_parse_from(std::iter::once("udc").chain(args.iter().copied()))?
+            .deployed_address()
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_not_unique_deployment() {
+        // Not-unique deployments are the same as deploying from the zero address
+        assert_eq!(
+            deployed_address(&["--not-unique", "--salt", SALT, CLASS_HASH, "0x1"])
+                .await
+                .unwrap(),
+            felt!("0x00da27ef7c3869c3a6cc6a0f7bf07a51c3e590825adba8a51cae27d815839eec")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unique_deployment() {
+        let deployer_address = felt!("0x2222");
+        let expected = get_contract_address(
+            pedersen_hash(&deployer_address, &Felt::from_hex(SALT).unwrap()),
+            Felt::from_hex(CLASS_HASH).unwrap(),
+            &[Felt::ONE],
+            DEFAULT_UDC_ADDRESS,
+        );
+
+        // Arguments go through the usual decoding
+        assert_eq!(
+            deployed_address(&[
+                "--deployer-address",
+                "u16:0x2222",
+                "--salt",
+                SALT,
+                CLASS_HASH,
+                "u8:1"
+            ])
+            .await
+            .unwrap(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_deployer_address() {
+        assert!(deployed_address(&["--salt", SALT, CLASS_HASH])
+            .await
+            .is_err());
+        assert!(deployed_address(&[
+            "--not-unique",
+            "--deployer-address",
+            "0x2222",
+            "--salt",
+            SALT,
+            CLASS_HASH
+        ])
+        .await
+        .is_err());
+        // Address book names need network access
+        assert!(deployed_address(&[
+            "--deployer-address",
+            "addr:alice",
+            "--salt",
+            SALT,
+            CLASS_HASH
+        ])
+        .await
+        .is_err());
+    }
+}
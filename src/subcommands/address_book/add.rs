@@ -0,0 +1,73 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use indexmap::map::Entry;
+use starknet::core::{types::Felt, utils::parse_cairo_short_string};
+
+use crate::address_book::{validate_name, validate_token_name, AddressBook, NamedAddress};
+
+use super::ChainIdArgs;
+
+#[derive(Debug, Parser)]
+pub struct Add {
+    #[clap(flatten)]
+    chain_id: ChainIdArgs,
+    #[clap(long, help = "Overwrite the address if the name already exists")]
+    force: bool,
+    #[clap(
+        long,
+        help = "Decimals of the token at the address, enabling amounts like `<NAME>:1.5`"
+    )]
+    decimals: Option<u8>,
+    #[clap(help = "Name to be used with the addr: scheme")]
+    name: String,
+    #[clap(help = "Contract address")]
+    address: Felt,
+}
+
+impl Add {
+    pub async fn run(self) -> Result<()> {
+        validate_name(&self.name)?;
+        if self.decimals.is_some() {
+            validate_token_name(&self.name)?;
+        }
+
+        let chain_id = self.chain_id.resolve().await?;
+        let entry_value = match self.decimals {
+            Some(decimals) => NamedAddress::Token {
+                address: self.address,
+                decimals,
+            },
+            None => NamedAddress::Address(self.address),
+        };
+
+        let mut address_book = AddressBook::load()?;
+        match address_book.chain_mut(chain_id)?.entry(self.name.clone()) {
+            Entry::Occupied(mut entry) => {
+                if !self.force {
+                    anyhow::bail!(
+                        "name `{}` already exists with address {:#064x}. Use --force to overwrite",
+                        self.name,
+                        entry.get().address()
+                    );
+                }
+
+                entry.insert(entry_value);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(entry_value);
+            }
+        }
+
+        address_book.save()?;
+
+        eprintln!(
+            "Address {} saved as {} for chain {}",
+            format!("{:#064x}", self.address).bright_yellow(),
+            format!("`{}`", self.name).bright_yellow(),
+            parse_cairo_short_string(&chain_id)?.bright_yellow()
+        );
+
+        Ok(())
+    }
+}
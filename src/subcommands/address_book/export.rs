@@ -0,0 +1,39 @@
+use std::{io::Write, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::{address_book::AddressBook, path::ExpandedPathbufParser};
+
+#[derive(Debug, Parser)]
+pub struct Export {
+    #[clap(
+        long,
+        short,
+        value_parser = ExpandedPathbufParser,
+        help = "Path to save the exported file to instead of printing it"
+    )]
+    output: Option<PathBuf>,
+}
+
+impl Export {
+    pub fn run(self) -> Result<()> {
+        let serialized = toml::to_string_pretty(&AddressBook::load()?)?;
+
+        match self.output {
+            Some(output) => {
+                let mut file = std::fs::File::create(&output)?;
+                file.write_all(serialized.as_bytes())?;
+
+                eprintln!(
+                    "Address book exported to {}",
+                    output.display().to_string().bright_yellow()
+                );
+            }
+            None => print!("{}", serialized),
+        }
+
+        Ok(())
+    }
+}
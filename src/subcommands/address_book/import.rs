@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::{address_book::AddressBook, path::ExpandedPathbufParser};
+
+#[derive(Debug, Parser)]
+pub struct Import {
+    #[clap(
+        long,
+        help = "Overwrite existing names with addresses from the imported file"
+    )]
+    force: bool,
+    #[clap(
+        value_parser = ExpandedPathbufParser,
+        help = "Path to the address book file to import"
+    )]
+    file: PathBuf,
+}
+
+impl Import {
+    pub fn run(self) -> Result<()> {
+        let imported = AddressBook::load_from(&self.file)?;
+        let mut address_book = AddressBook::load()?;
+
+        let mut count = 0;
+        for (chain_id, entries) in imported.chains.into_iter() {
+            let existing_entries = address_book.chains.entry(chain_id.clone()).or_default();
+
+            for (name, entry) in entries.into_iter() {
+                match existing_entries.get(&name) {
+                    Some(existing_entry) if *existing_entry == entry => continue,
+                    Some(existing_entry) if !self.force => {
+                        anyhow::bail!(
+                            "name `{}` already exists for chain {} with a different entry \
+                            {:#064x}. Use --force to overwrite",
+                            name,
+                            chain_id,
+                            existing_entry.address()
+                        );
+                    }
+                    _ => {
+                        existing_entries.insert(name, entry);
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        address_book.save()?;
+
+        eprintln!(
+            "{} entries imported from {}",
+            count.to_string().bright_yellow(),
+            self.file.display().to_string().bright_yellow()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use starknet::core::utils::{cairo_short_string_to_felt, parse_cairo_short_string};
+
+use crate::{
+    address_book::{AddressBook, HARDCODED_ADDRESS_BOOK},
+    config::ProjectConfig,
+};
+
+#[derive(Debug, Parser)]
+pub struct List {
+    #[clap(
+        long,
+        help = "Only list entries of a chain ID in Cairo short string representation, such as \
+        SN_SEPOLIA"
+    )]
+    chain_id: Option<String>,
+}
+
+impl List {
+    pub fn run(self) -> Result<()> {
+        let chain_id_filter = self
+            .chain_id
+            .map(|chain_id| {
+                cairo_short_string_to_felt(&chain_id)
+                    .map_err(|_| anyhow::anyhow!("invalid chain ID \"{}\"", chain_id))
+            })
+            .transpose()?;
+
+        // Listed in the same order as names are resolved
+        let mut entries = vec![];
+
+        for (chain_id, chain_entries) in AddressBook::load()?.chains.iter() {
+            for (name, entry) in chain_entries.iter() {
+                entries.push((
+                    chain_id.to_owned(),
+                    name.to_owned(),
+                    entry.address(),
+                    entry.decimals(),
+                    "user",
+                ));
+            }
+        }
+
+        if let Some(project) = ProjectConfig::load()? {
+            for (chain_id, chain_entries) in project.address_book.chains.iter() {
+                for (name, entry) in chain_entries.iter() {
+                    entries.push((
+                        chain_id.to_owned(),
+                        name.to_owned(),
+                        entry.address(),
+                        entry.decimals(),
+                        "project",
+                    ));
+                }
+            }
+        }
+
+        for entry in HARDCODED_ADDRESS_BOOK.iter() {
+            entries.push((
+                parse_cairo_short_string(&entry.chain_id)?,
+                entry.name.to_owned(),
+                entry.address,
+                entry.decimals,
+                "built-in",
+            ));
+        }
+
+        for (chain_id, name, address, decimals, source) in entries.into_iter() {
+            if let Some(chain_id_filter) = chain_id_filter {
+                // Chain IDs have all been validated on load
+                if cairo_short_string_to_felt(&chain_id)? != chain_id_filter {
+                    continue;
+                }
+            }
+
+            match decimals {
+                Some(decimals) => println!(
+                    "{} {}: {:#064x} ({} decimals) [{}]",
+                    chain_id,
+                    name.bright_yellow(),
+                    address,
+                    decimals,
+                    source
+                ),
+                None => println!(
+                    "{} {}: {:#064x} [{}]",
+                    chain_id,
+                    name.bright_yellow(),
+                    address,
+                    source
+                ),
+            }
+        }
+
+        Ok(())
+    }
+}
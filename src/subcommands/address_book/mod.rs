@@ -0,0 +1,75 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use starknet::core::{types::Felt, utils::cairo_short_string_to_felt};
+
+use crate::{chain_id::ChainIdSource, provider::ProviderArgs};
+
+mod add;
+use add::Add;
+
+mod remove;
+use remove::Remove;
+
+mod list;
+use list::List;
+
+mod import;
+use import::Import;
+
+mod export;
+use export::Export;
+
+#[derive(Debug, Parser)]
+pub struct AddressBook {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    #[clap(about = "Add a named address for a chain")]
+    Add(Add),
+    #[clap(about = "Remove a named address from a chain")]
+    Remove(Remove),
+    #[clap(about = "List named addresses from all sources")]
+    List(List),
+    #[clap(about = "Import entries from an address book file")]
+    Import(Import),
+    #[clap(about = "Export the address book file")]
+    Export(Export),
+}
+
+/// Chain selection for commands that modify entries of a single chain.
+#[derive(Debug, Clone, Parser)]
+struct ChainIdArgs {
+    #[clap(
+        long,
+        help = "Chain ID in Cairo short string representation, such as SN_SEPOLIA. Fetched from \
+        the provider if not supplied"
+    )]
+    chain_id: Option<String>,
+    #[clap(flatten)]
+    provider: ProviderArgs,
+}
+
+impl AddressBook {
+    pub async fn run(self) -> Result<()> {
+        match self.command {
+            Subcommands::Add(cmd) => cmd.run().await,
+            Subcommands::Remove(cmd) => cmd.run().await,
+            Subcommands::List(cmd) => cmd.run(),
+            Subcommands::Import(cmd) => cmd.run(),
+            Subcommands::Export(cmd) => cmd.run(),
+        }
+    }
+}
+
+impl ChainIdArgs {
+    async fn resolve(self) -> Result<Felt> {
+        match self.chain_id {
+            Some(chain_id) => cairo_short_string_to_felt(&chain_id)
+                .map_err(|_| anyhow::anyhow!("invalid chain ID \"{}\"", chain_id)),
+            None => self.provider.into_provider()?.get_chain_id().await,
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use starknet::core::utils::parse_cairo_short_string;
+
+use crate::address_book::AddressBook;
+
+use super::ChainIdArgs;
+
+#[derive(Debug, Parser)]
+pub struct Remove {
+    #[clap(flatten)]
+    chain_id: ChainIdArgs,
+    #[clap(help = "Name to be removed")]
+    name: String,
+}
+
+impl Remove {
+    pub async fn run(self) -> Result<()> {
+        let chain_id = self.chain_id.resolve().await?;
+        let chain_name = parse_cairo_short_string(&chain_id)?;
+
+        let mut address_book = AddressBook::load()?;
+        let entries = address_book.chain_mut(chain_id)?;
+
+        if entries.shift_remove(&self.name).is_none() {
+            anyhow::bail!(
+                "name `{}` is not in the address book for chain {}",
+                self.name,
+                chain_name
+            );
+        }
+
+        // Empty chains are not kept around
+        if entries.is_empty() {
+            address_book.chains.shift_remove(&chain_name);
+        }
+
+        address_book.save()?;
+
+        eprintln!(
+            "Name {} removed for chain {}",
+            format!("`{}`", self.name).bright_yellow(),
+            chain_name.bright_yellow()
+        );
+
+        Ok(())
+    }
+}
@@ -11,7 +11,11 @@ use starknet::{
 };
 
 use crate::{
-    address_book::AddressBookResolver, decode::FeltDecoder, verbosity::VerbosityArgs, ProviderArgs,
+    account::{AccountArgs, SelfAccountResolver},
+    address_book::AddressBookResolver,
+    decode::FeltDecoder,
+    verbosity::VerbosityArgs,
+    ProviderArgs,
 };
 
 /// The default ETH address: 0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7.
@@ -22,6 +26,8 @@ const DEFAULT_ETH_ADDRESS: Felt =
 pub struct Balance {
     #[clap(flatten)]
     provider: ProviderArgs,
+    #[clap(flatten)]
+    account: AccountArgs,
     #[clap(help = "Account address")]
     account_address: String,
     #[clap(
@@ -45,7 +51,9 @@ impl Balance {
         self.verbosity.setup_logging();
 
         let provider = Arc::new(self.provider.into_provider()?);
-        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+        // Account options are only used for the `self` scheme
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()))
+            .with_self_account(SelfAccountResolver::new(self.account, provider.clone()));
 
         let account_address = felt_decoder
             .decode_single_with_addr_fallback(&self.account_address)
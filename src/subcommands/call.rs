@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
@@ -8,8 +8,17 @@ use starknet::{
 };
 
 use crate::{
-    address_book::AddressBookResolver, block_id::BlockIdParser, decode::FeltDecoder,
-    error::provider_error_mapper, verbosity::VerbosityArgs, ProviderArgs,
+    abi::{AbiArgs, ContractAbi},
+    account::{AccountArgs, SelfAccountResolver},
+    address_book::AddressBookResolver,
+    block_id::BlockIdParser,
+    calls_file::CalldataFile,
+    decode::FeltDecoder,
+    error::provider_error_mapper,
+    path::ExpandedPathbufParser,
+    utils::print_colored_json,
+    verbosity::VerbosityArgs,
+    ProviderArgs,
 };
 
 #[derive(Debug, Parser)]
@@ -23,11 +32,35 @@ pub struct Call {
         help = "Block number, hash, or tag (latest/pending)"
     )]
     block: BlockId,
+    #[clap(flatten)]
+    account: AccountArgs,
+    #[clap(flatten)]
+    abi: AbiArgs,
+    #[clap(
+        long,
+        help = "Decode the return values into JSON based on the contract ABI, which is taken from \
+        --abi if supplied"
+    )]
+    decode: bool,
+    #[clap(
+        long,
+        conflicts_with = "decode",
+        help = "Decode the return values with comma-separated types instead of the contract ABI \
+        (e.g. u256,bytearray)"
+    )]
+    output_types: Option<String>,
     #[clap(help = "Contract address")]
     contract_address: String,
     #[clap(help = "Name of the function being called")]
     selector: String,
-    #[clap(help = "Raw function call arguments")]
+    #[clap(
+        long,
+        value_parser = ExpandedPathbufParser,
+        conflicts_with = "calldata",
+        help = "Path to a JSON or TOML file with the function call arguments, or `-` for stdin"
+    )]
+    calldata_file: Option<PathBuf>,
+    #[clap(help = "Raw function call arguments, or typed ones with --typed")]
     calldata: Vec<String>,
     #[clap(flatten)]
     verbosity: VerbosityArgs,
@@ -38,7 +71,9 @@ impl Call {
         self.verbosity.setup_logging();
 
         let provider = Arc::new(self.provider.into_provider()?);
-        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+        // Account options are only used for the `self` scheme
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()))
+            .with_self_account(SelfAccountResolver::new(self.account, provider.clone()));
 
         let contract_address = felt_decoder
             .decode_single_with_addr_fallback(&self.contract_address)
@@ -47,10 +82,32 @@ impl Call {
             .decode_single_with_selector_fallback(&self.selector)
             .await?;
 
-        let mut calldata = vec![];
-        for element in self.calldata.iter() {
-            calldata.append(&mut felt_decoder.decode(element).await?);
-        }
+        let args = match &self.calldata_file {
+            Some(calldata_file) => CalldataFile::load(calldata_file)?.calldata,
+            None => self.calldata,
+        };
+
+        // The ABI is loaded only once when used for both arguments and return values
+        let abi = if self.abi.is_typed() || self.decode {
+            Some(
+                self.abi
+                    .load_for_contract(&provider, self.block, contract_address)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let calldata = if let (true, Some(abi)) = (self.abi.is_typed(), &abi) {
+            abi.encode_function_arguments(selector, &args, &felt_decoder)
+                .await?
+        } else {
+            let mut calldata = vec![];
+            for element in args.iter() {
+                calldata.append(&mut felt_decoder.decode(element).await?);
+            }
+            calldata
+        };
 
         let result = provider
             .call(
@@ -64,6 +121,16 @@ impl Call {
             .await
             .map_err(provider_error_mapper)?;
 
+        if let Some(output_types) = &self.output_types {
+            print_colored_json(&ContractAbi::decode_with_type_hints(output_types, &result)?)?;
+            return Ok(());
+        }
+
+        if let (true, Some(abi)) = (self.decode, &abi) {
+            print_colored_json(&abi.decode_function_outputs(selector, &result)?)?;
+            return Ok(());
+        }
+
         if result.is_empty() {
             println!("[]");
         } else {
@@ -0,0 +1,25 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod show;
+use show::Show;
+
+#[derive(Debug, Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    #[clap(about = "Show the effective configuration and where each value comes from")]
+    Show(Show),
+}
+
+impl Config {
+    pub fn run(self) -> Result<()> {
+        match self.command {
+            Subcommands::Show(cmd) => cmd.run(),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::fmt::Display;
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use starknet::core::utils::parse_cairo_short_string;
+
+use crate::{
+    config::{ProjectConfig, DEFAULT_POLL_INTERVAL},
+    profile::{ProfileArgs, Profiles, DEFAULT_PROFILE_NAME},
+};
+
+#[derive(Debug, Parser)]
+pub struct Show {
+    #[clap(flatten)]
+    profile: ProfileArgs,
+}
+
+enum ValueSource {
+    EnvVar(&'static str),
+    ProjectFile,
+    Profile(String),
+    Network(String),
+    Default,
+}
+
+impl Show {
+    pub fn run(self) -> Result<()> {
+        let profile = self.profile.name();
+
+        let project = ProjectConfig::load()?;
+        let profiles = Profiles::load()?;
+
+        let profile_networks = match profiles.profiles.get(profile) {
+            Some(matched_profile) => Some(&matched_profile.networks),
+            // An absent `default` profile is simply an empty one
+            None if profile == DEFAULT_PROFILE_NAME => None,
+            None => anyhow::bail!("profile `{}` is not defined", profile),
+        };
+
+        match &project {
+            Some(project) => println!(
+                "Project file: {}",
+                project.path.display().to_string().bright_yellow()
+            ),
+            None => println!("Project file: {}", "not found".bright_yellow()),
+        }
+        println!("Profile: {}", profile.bright_yellow());
+        println!();
+
+        let rpc = from_env("STARKNET_RPC");
+        let network = from_env("STARKNET_NETWORK").or_else(|| {
+            // Neither the project network nor the fallback one is used when an RPC URL is supplied
+            if rpc.is_some() {
+                return None;
+            }
+
+            Some(
+                project
+                    .as_ref()
+                    .and_then(|project| project.network.clone())
+                    .map(|network| (network, ValueSource::ProjectFile))
+                    .unwrap_or_else(|| ("sepolia".into(), ValueSource::Default)),
+            )
+        });
+
+        // Network defaults are only available when the network is resolved from its identifier
+        let network_config = match (&rpc, &network) {
+            (None, Some((network, _))) => project
+                .as_ref()
+                .and_then(|project| project.networks.get(network))
+                .or_else(|| profile_networks.and_then(|networks| networks.get(network)))
+                .map(|network_config| (network.to_owned(), network_config)),
+            _ => None,
+        };
+
+        let account = from_env("STARKNET_ACCOUNT")
+            .or_else(|| {
+                project
+                    .as_ref()
+                    .and_then(|project| project.resolved_account())
+                    .map(|account| (account, ValueSource::ProjectFile))
+            })
+            .or_else(|| {
+                network_config.as_ref().and_then(|(id, network_config)| {
+                    network_config
+                        .account
+                        .clone()
+                        .map(|account| (account, ValueSource::Network(id.to_owned())))
+                })
+            });
+        let signer = from_env("STARKNET_KEYSTORE")
+            .map(|(keystore, source)| (format!("keystore {}", keystore), source))
+            .or_else(|| {
+                // Private keys are never displayed
+                from_env("STARKNET_PRIVATE_KEY")
+                    .map(|(_, source)| ("private key".to_owned(), source))
+            })
+            .or_else(|| {
+                from_env("STARKNET_LEDGER_PATH")
+                    .map(|(path, source)| (format!("Ledger {}", path), source))
+            })
+            .or_else(|| {
+                project
+                    .as_ref()
+                    .and_then(|project| project.resolved_keystore())
+                    .map(|keystore| (format!("keystore {}", keystore), ValueSource::ProjectFile))
+            })
+            .or_else(|| {
+                network_config.as_ref().and_then(|(id, network_config)| {
+                    network_config
+                        .signer
+                        .as_ref()
+                        .map(|signer| (signer.to_string(), ValueSource::Network(id.to_owned())))
+                })
+            });
+        let poll_interval = from_env("STARKNET_POLL_INTERVAL")
+            .or_else(|| {
+                project
+                    .as_ref()
+                    .and_then(|project| project.poll_interval)
+                    .map(|poll_interval| (poll_interval.to_string(), ValueSource::ProjectFile))
+            })
+            .unwrap_or_else(|| (DEFAULT_POLL_INTERVAL.to_string(), ValueSource::Default));
+
+        print_setting("rpc", rpc);
+        print_setting("network", network);
+        print_setting("account", account);
+        print_setting("signer", signer);
+        print_setting("poll_interval", Some(poll_interval));
+
+        if let Some(project) = &project {
+            let fee = &project.fee;
+            for (key, value) in [
+                ("fee.l1_gas", &fee.l1_gas),
+                ("fee.l1_gas_price", &fee.l1_gas_price),
+                ("fee.l1_gas_price_raw", &fee.l1_gas_price_raw),
+                ("fee.l2_gas", &fee.l2_gas),
+                ("fee.l2_gas_price", &fee.l2_gas_price),
+                ("fee.l2_gas_price_raw", &fee.l2_gas_price_raw),
+                ("fee.l1_data_gas", &fee.l1_data_gas),
+                ("fee.l1_data_gas_price", &fee.l1_data_gas_price),
+                ("fee.l1_data_gas_price_raw", &fee.l1_data_gas_price_raw),
+            ] {
+                if let Some(value) = value {
+                    print_setting(key, Some((value.to_owned(), ValueSource::ProjectFile)));
+                }
+            }
+        }
+
+        println!();
+        println!("Networks:");
+
+        if let Some(project) = &project {
+            for (id, network) in project.networks.iter() {
+                println!(
+                    "  {} ({}): {} [{}]",
+                    id.bright_yellow(),
+                    parse_cairo_short_string(&network.chain_id)?,
+                    network.provider,
+                    ValueSource::ProjectFile
+                );
+            }
+        }
+
+        if let Some(profile_networks) = profile_networks {
+            for (id, network) in profile_networks.iter() {
+                println!(
+                    "  {} ({}): {} [{}]",
+                    id.bright_yellow(),
+                    parse_cairo_short_string(&network.chain_id)?,
+                    network.provider,
+                    ValueSource::Profile(profile.to_owned())
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for ValueSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EnvVar(name) => write!(f, "env {}", name),
+            Self::ProjectFile => write!(f, "project file"),
+            Self::Profile(name) => write!(f, "profile `{}`", name),
+            Self::Network(id) => write!(f, "network `{}`", id),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+fn from_env(name: &'static str) -> Option<(String, ValueSource)> {
+    std::env::var(name)
+        .ok()
+        .map(|value| (value, ValueSource::EnvVar(name)))
+}
+
+fn print_setting(key: &str, value: Option<(String, ValueSource)>) {
+    match value {
+        Some((value, source)) => println!("{:<26} {} [{}]", key, value.bright_yellow(), source),
+        None => println!("{:<26} -", key),
+    }
+}
@@ -1,4 +1,4 @@
-use std::{path::PathBuf, sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
@@ -17,6 +17,7 @@ use crate::{
     account::AccountArgs,
     casm::{CasmArgs, CasmHashSource},
     compiler::BuiltInCompiler,
+    config::resolve_poll_interval,
     error::account_error_mapper,
     fee::{FeeArgs, FeeSetting, TokenFeeSetting},
     path::ExpandedPathbufParser,
@@ -46,10 +47,9 @@ pub struct Declare {
     #[clap(
         long,
         env = "STARKNET_POLL_INTERVAL",
-        default_value = "5000",
-        help = "Transaction result poll interval in milliseconds"
+        help = "Transaction result poll interval in milliseconds [default: 5000]"
     )]
-    poll_interval: u64,
+    poll_interval: Option<u64>,
     #[clap(
         value_parser = ExpandedPathbufParser,
         help = "Path to contract artifact file"
@@ -67,13 +67,13 @@ impl Declare {
     pub async fn run(self) -> Result<()> {
         self.verbosity.setup_logging();
 
-        let fee_setting = self.fee.into_setting()?;
+        let provider = Arc::new(self.provider.into_provider()?);
+
+        let fee_setting = self.fee.into_setting(provider.project_config())?;
         if self.simulate && fee_setting.is_estimate_only() {
             anyhow::bail!("--simulate cannot be used with --estimate-only");
         }
 
-        let provider = Arc::new(self.provider.into_provider()?);
-
         let account = self.account.into_account(provider.clone()).await?;
 
         // Working around a deserialization bug in `starknet-rs`:
@@ -248,6 +248,8 @@ impl Declare {
                             return Ok(());
                         }
 
+                        provider.verify_chain_id().await?;
+
                         declaration.send().await
                     }
                 }
@@ -271,7 +273,7 @@ impl Declare {
             watch_tx(
                 &provider,
                 declaration_tx_hash,
-                Duration::from_millis(self.poll_interval),
+                resolve_poll_interval(self.poll_interval, provider.project_config()),
             )
             .await?;
         }
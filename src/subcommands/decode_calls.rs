@@ -0,0 +1,318 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Result;
+use clap::{builder::PossibleValue, Parser, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use starknet::{
+    accounts::ExecutionEncoding,
+    core::types::{
+        BlockId, BlockTag, ContractClass, Felt, InvokeTransaction, StarknetError, Transaction,
+    },
+    providers::{Provider, ProviderError},
+};
+
+use crate::{
+    abi::ContractAbi, account::KNOWN_ACCOUNT_CLASSES, address_book::AddressBookResolver,
+    decode::FeltDecoder, error::provider_error_mapper, provider::ExtendedProvider,
+    utils::print_colored_json, verbosity::VerbosityArgs, ProviderArgs,
+};
+
+#[derive(Debug, Parser)]
+pub struct DecodeCalls {
+    #[clap(flatten)]
+    provider: ProviderArgs,
+    #[clap(
+        long,
+        conflicts_with_all = ["sender", "calldata"],
+        help = "Hash of an INVOKE transaction to decode the calldata of"
+    )]
+    transaction: Option<String>,
+    #[clap(
+        long,
+        help = "Address of the account sending the calldata, for detecting the encoding"
+    )]
+    sender: Option<String>,
+    #[clap(
+        long,
+        help = "Calldata encoding. Detected from the account class if not supplied"
+    )]
+    encoding: Option<CalldataEncoding>,
+    #[clap(
+        long,
+        help = "Do not look up function names from the ABIs of the target contracts"
+    )]
+    no_names: bool,
+    #[clap(
+        required_unless_present = "transaction",
+        help = "Raw `__execute__` calldata"
+    )]
+    calldata: Vec<String>,
+    #[clap(flatten)]
+    verbosity: VerbosityArgs,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CalldataEncoding {
+    New,
+    Legacy,
+}
+
+#[derive(Serialize)]
+struct DecodedCall {
+    to: Felt,
+    selector: Felt,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    function: Option<String>,
+    calldata: Vec<Felt>,
+}
+
+impl DecodeCalls {
+    pub async fn run(self) -> Result<()> {
+        self.verbosity.setup_logging();
+
+        let provider = Arc::new(self.provider.into_provider()?);
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+
+        let (sender, calldata) = match &self.transaction {
+            Some(transaction_hash) => {
+                let transaction_hash = Felt::from_hex(transaction_hash)?;
+                match provider
+                    .get_transaction_by_hash(transaction_hash)
+                    .await
+                    .map_err(provider_error_mapper)?
+                {
+                    Transaction::Invoke(InvokeTransaction::V1(tx)) => {
+                        (Some(tx.sender_address), tx.calldata)
+                    }
+                    Transaction::Invoke(InvokeTransaction::V3(tx)) => {
+                        (Some(tx.sender_address), tx.calldata)
+                    }
+                    Transaction::Invoke(InvokeTransaction::V0(_)) => {
+                        anyhow::bail!("INVOKE v0 transactions are not sent from accounts")
+                    }
+                    _ => anyhow::bail!("not an INVOKE transaction"),
+                }
+            }
+            None => {
+                let sender = match &self.sender {
+                    Some(sender) => Some(
+                        felt_decoder
+                            .decode_single_with_addr_fallback(sender)
+                            .await?,
+                    ),
+                    None => None,
+                };
+
+                let mut calldata = vec![];
+                for element in self.calldata.iter() {
+                    calldata.append(&mut felt_decoder.decode(element).await?);
+                }
+
+                (sender, calldata)
+            }
+        };
+
+        let encoding = match self.encoding {
+            Some(CalldataEncoding::New) => Some(ExecutionEncoding::New),
+            Some(CalldataEncoding::Legacy) => Some(ExecutionEncoding::Legacy),
+            None => match sender {
+                Some(sender) => detect_encoding(&provider, sender).await?,
+                None => None,
+            },
+        };
+
+        let mut calls = match encoding {
+            Some(ExecutionEncoding::New) => decode_new_calldata(&calldata)?,
+            Some(ExecutionEncoding::Legacy) => decode_legacy_calldata(&calldata)?,
+            None => {
+                // Calldata valid in both encodings is rare enough to not be worth rejecting
+                match decode_new_calldata(&calldata) {
+                    Ok(calls) => calls,
+                    Err(new_err) => decode_legacy_calldata(&calldata).map_err(|legacy_err| {
+                        anyhow::anyhow!(
+                            "unable to decode calldata with either the new ({}) or the legacy \
+                            ({}) encoding",
+                            new_err,
+                            legacy_err
+                        )
+                    })?,
+                }
+            }
+        };
+
+        if !self.no_names {
+            let mut abis: HashMap<Felt, Option<ContractAbi>> = HashMap::new();
+
+            for call in calls.iter_mut() {
+                if !abis.contains_key(&call.to) {
+                    let abi = match provider
+                        .get_class_at(BlockId::Tag(BlockTag::Pending), call.to)
+                        .await
+                    {
+                        Ok(class @ ContractClass::Sierra(_)) => {
+                            Some(ContractAbi::from_class(class)?)
+                        }
+                        // Legacy classes and contracts that don't exist are simply left unnamed
+                        Ok(ContractClass::Legacy(_))
+                        | Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => {
+                            None
+                        }
+                        Err(err) => return Err(provider_error_mapper(err)),
+                    };
+                    abis.insert(call.to, abi);
+                }
+
+                call.function = abis[&call.to]
+                    .as_ref()
+                    .and_then(|abi| abi.function_name(call.selector))
+                    .map(|name| name.to_owned());
+            }
+        }
+
+        print_colored_json(&calls)?;
+
+        Ok(())
+    }
+}
+
+impl ValueEnum for CalldataEncoding {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::New, Self::Legacy]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            Self::New => Some(PossibleValue::new("new")),
+            Self::Legacy => Some(PossibleValue::new("legacy").alias("cairo0")),
+        }
+    }
+}
+
+/// Looks up the encoding from the class of the account, if it's a known one.
+async fn detect_encoding(
+    provider: &ExtendedProvider,
+    sender: Felt,
+) -> Result<Option<ExecutionEncoding>> {
+    let class_hash = provider
+        .get_class_hash_at(BlockId::Tag(BlockTag::Pending), sender)
+        .await
+        .map_err(provider_error_mapper)?;
+
+    match KNOWN_ACCOUNT_CLASSES
+        .iter()
+        .find(|class| class.class_hash == class_hash)
+    {
+        Some(class) => Ok(Some(class.variant.default_execution_encoding())),
+        None => {
+            eprintln!(
+                "{}",
+                format!(
+                    "NOTE: {class_hash:#064x} is not a known account class hash. \
+                    Guessing the calldata encoding instead."
+                )
+                .bright_magenta()
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Decodes `[calls_len, (to, selector, calldata_len, calldata...)...]`.
+fn decode_new_calldata(calldata: &[Felt]) -> Result<Vec<DecodedCall>> {
+    let mut iter = calldata.iter();
+    let calls_len = next_len(&mut iter, "calls_len")?;
+
+    let mut calls = vec![];
+    for _ in 0..calls_len {
+        let to = *iter
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("unexpected end of calldata"))?;
+        let selector = *iter
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("unexpected end of calldata"))?;
+        let data_len = next_len(&mut iter, "calldata_len")?;
+
+        let mut data = vec![];
+        for _ in 0..data_len {
+            data.push(
+                *iter
+                    .next()
+                    .ok_or_else(|| anyhow::anyhow!("unexpected end of calldata"))?,
+            );
+        }
+
+        calls.push(DecodedCall {
+            to,
+            selector,
+            function: None,
+            calldata: data,
+        });
+    }
+
+    if iter.next().is_some() {
+        anyhow::bail!("unexpected trailing calldata");
+    }
+
+    Ok(calls)
+}
+
+/// Decodes `[call_array_len, (to, selector, data_offset, data_len)..., calldata_len, calldata...]`.
+fn decode_legacy_calldata(calldata: &[Felt]) -> Result<Vec<DecodedCall>> {
+    let mut iter = calldata.iter();
+    let call_array_len = next_len(&mut iter, "call_array_len")?;
+
+    let mut call_array = vec![];
+    for _ in 0..call_array_len {
+        let to = *iter
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("unexpected end of calldata"))?;
+        let selector = *iter
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("unexpected end of calldata"))?;
+        let data_offset = next_len(&mut iter, "data_offset")?;
+        let data_len = next_len(&mut iter, "data_len")?;
+
+        call_array.push((to, selector, data_offset, data_len));
+    }
+
+    let calldata_len = next_len(&mut iter, "calldata_len")?;
+    let data = iter.copied().collect::<Vec<_>>();
+    if data.len() != calldata_len {
+        anyhow::bail!(
+            "calldata_len is {} but {} elements remain",
+            calldata_len,
+            data.len()
+        );
+    }
+
+    call_array
+        .into_iter()
+        .map(|(to, selector, data_offset, data_len)| {
+            let data = data
+                .get(data_offset..(data_offset + data_len))
+                .ok_or_else(|| anyhow::anyhow!("call data out of bounds"))?;
+
+            Ok(DecodedCall {
+                to,
+                selector,
+                function: None,
+                calldata: data.to_vec(),
+            })
+        })
+        .collect()
+}
+
+fn next_len<'a, I>(iter: &mut I, name: &str) -> Result<usize>
+where
+    I: Iterator<Item = &'a Felt>,
+{
+    let value = iter
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("unexpected end of calldata"))?;
+
+    // Lengths can't possibly exceed the calldata size, so anything beyond `u32` is invalid anyway
+    u32::try_from(*value)
+        .map(|value| value as usize)
+        .map_err(|_| anyhow::anyhow!("invalid {}: {:#x}", name, value))
+}
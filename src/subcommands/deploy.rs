@@ -1,25 +1,29 @@
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
-use starknet::{contract::ContractFactory, core::types::Felt, macros::felt, signers::SigningKey};
+use starknet::{
+    contract::ContractFactory,
+    core::types::{BlockId, BlockTag, Felt},
+    signers::SigningKey,
+};
 
 use crate::{
-    account::AccountArgs,
+    abi::AbiArgs,
+    account::{AccountArgs, SelfAccountResolver},
     address_book::AddressBookResolver,
+    calls_file::CalldataFile,
+    config::resolve_poll_interval,
     decode::FeltDecoder,
     error::account_error_mapper,
     fee::{FeeArgs, FeeSetting, TokenFeeSetting},
-    utils::{felt_to_bigdecimal, print_colored_json, watch_tx},
+    path::ExpandedPathbufParser,
+    utils::{felt_to_bigdecimal, print_colored_json, watch_tx, DEFAULT_UDC_ADDRESS},
     verbosity::VerbosityArgs,
     ProviderArgs,
 };
 
-/// The default UDC address: 0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf.
-const DEFAULT_UDC_ADDRESS: Felt =
-    felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
-
 #[derive(Debug, Parser)]
 pub struct Deploy {
     #[clap(flatten)]
@@ -41,13 +45,21 @@ pub struct Deploy {
     #[clap(
         long,
         env = "STARKNET_POLL_INTERVAL",
-        default_value = "5000",
-        help = "Transaction result poll interval in milliseconds"
+        help = "Transaction result poll interval in milliseconds [default: 5000]"
     )]
-    poll_interval: u64,
+    poll_interval: Option<u64>,
+    #[clap(flatten)]
+    abi: AbiArgs,
     #[clap(help = "Class hash")]
     class_hash: String,
-    #[clap(help = "Raw constructor arguments")]
+    #[clap(
+        long,
+        value_parser = ExpandedPathbufParser,
+        conflicts_with = "ctor_args",
+        help = "Path to a JSON or TOML file with the constructor arguments, or `-` for stdin"
+    )]
+    calldata_file: Option<PathBuf>,
+    #[clap(help = "Raw constructor arguments, or typed ones with --typed")]
     ctor_args: Vec<String>,
     #[clap(flatten)]
     verbosity: VerbosityArgs,
@@ -57,19 +69,37 @@ impl Deploy {
     pub async fn run(self) -> Result<()> {
         self.verbosity.setup_logging();
 
-        let fee_setting = self.fee.into_setting()?;
+        let provider = Arc::new(self.provider.into_provider()?);
+
+        let fee_setting = self.fee.into_setting(provider.project_config())?;
         if self.simulate && fee_setting.is_estimate_only() {
             anyhow::bail!("--simulate cannot be used with --estimate-only");
         }
 
-        let provider = Arc::new(self.provider.into_provider()?);
-        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+        // Resolved upfront so that the signer is only loaded once even when `self:` is used
+        let account = self.account.resolve(&provider).await?;
+
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()))
+            .with_self_account(SelfAccountResolver::from_resolved(&account).await?);
 
         let class_hash = Felt::from_hex(&self.class_hash)?;
-        let mut ctor_args = vec![];
-        for element in self.ctor_args.iter() {
-            ctor_args.append(&mut felt_decoder.decode(element).await?);
-        }
+        let args = match &self.calldata_file {
+            Some(calldata_file) => CalldataFile::load(calldata_file)?.calldata,
+            None => self.ctor_args,
+        };
+        let ctor_args = if self.abi.is_typed() {
+            self.abi
+                .load_for_class(&provider, BlockId::Tag(BlockTag::Pending), class_hash)
+                .await?
+                .encode_constructor_arguments(&args, &felt_decoder)
+                .await?
+        } else {
+            let mut ctor_args = vec![];
+            for element in args.iter() {
+                ctor_args.append(&mut felt_decoder.decode(element).await?);
+            }
+            ctor_args
+        };
 
         let salt = if let Some(s) = self.salt {
             Felt::from_hex(&s)?
@@ -77,7 +107,7 @@ impl Deploy {
             SigningKey::from_random().secret_scalar()
         };
 
-        let account = self.account.into_account(provider.clone()).await?;
+        let account = account.into_account(provider.clone()).await?;
 
         // TODO: allow custom UDC
         let factory = ContractFactory::new_with_udc(class_hash, account, DEFAULT_UDC_ADDRESS);
@@ -161,6 +191,8 @@ impl Deploy {
                     return Ok(());
                 }
 
+                provider.verify_chain_id().await?;
+
                 contract_deployment.send().await
             }
         }
@@ -180,7 +212,7 @@ impl Deploy {
             watch_tx(
                 &provider,
                 deployment_tx,
-                Duration::from_millis(self.poll_interval),
+                resolve_poll_interval(self.poll_interval, provider.project_config()),
             )
             .await?;
         }
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::{
+    address_book::AddressBookResolver, chain_id::OfflineSource, decode::FeltDecoder,
+    hash::HashFunction,
+};
+
+#[derive(Debug, Parser)]
+pub struct Hash {
+    #[clap(long, help = "Display the hash in decimal representation")]
+    dec: bool,
+    #[clap(
+        help = "Hash function to use. Note that `keccak` hashes elements as 32-byte big-endian \
+        words, not the bytes of strings, so use `starkli selector` to hash a name instead"
+    )]
+    function: HashFunction,
+    #[clap(help = "Elements to be hashed, resolved the same way as calldata")]
+    inputs: Vec<String>,
+}
+
+impl Hash {
+    pub async fn run(self) -> Result<()> {
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(OfflineSource));
+
+        let mut elements = vec![];
+        for input in self.inputs.iter() {
+            elements.append(&mut felt_decoder.decode(input).await?);
+        }
+
+        let hash = self.function.hash(&elements)?;
+        if self.dec {
+            println!("{hash}");
+        } else {
+            println!("{hash:#064x}");
+        }
+
+        Ok(())
+    }
+}
@@ -1,19 +1,27 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use anyhow::Result;
 use clap::Parser;
 use colored::Colorize;
 use starknet::{
     accounts::Account,
-    core::types::{Call, Felt},
+    core::types::{BlockId, BlockTag, Call, Felt},
 };
 
 use crate::{
-    account::AccountArgs,
+    abi::{AbiArgs, ContractAbi},
+    account::{AccountArgs, SelfAccountResolver},
     address_book::AddressBookResolver,
+    calls_file::{CallsFile, RawCall},
+    config::resolve_poll_interval,
     decode::FeltDecoder,
     error::account_error_mapper,
     fee::{FeeArgs, FeeSetting, TokenFeeSetting},
+    path::ExpandedPathbufParser,
     utils::{felt_to_bigdecimal, print_colored_json, watch_tx},
     verbosity::VerbosityArgs,
     ProviderArgs,
@@ -36,10 +44,18 @@ pub struct Invoke {
     #[clap(
         long,
         env = "STARKNET_POLL_INTERVAL",
-        default_value = "5000",
-        help = "Transaction result poll interval in milliseconds"
+        help = "Transaction result poll interval in milliseconds [default: 5000]"
+    )]
+    poll_interval: Option<u64>,
+    #[clap(flatten)]
+    abi: AbiArgs,
+    #[clap(
+        long,
+        value_parser = ExpandedPathbufParser,
+        conflicts_with = "calls",
+        help = "Path to a JSON or TOML file with the contract calls, or `-` for stdin"
     )]
-    poll_interval: u64,
+    calls_file: Option<PathBuf>,
     #[clap(help = "One or more contract calls. See documentation for more details")]
     calls: Vec<String>,
     #[clap(flatten)]
@@ -50,42 +66,106 @@ impl Invoke {
     pub async fn run(self) -> Result<()> {
         self.verbosity.setup_logging();
 
-        let fee_setting = self.fee.into_setting()?;
+        let provider = Arc::new(self.provider.into_provider()?);
+
+        let fee_setting = self.fee.into_setting(provider.project_config())?;
         if self.simulate && fee_setting.is_estimate_only() {
             anyhow::bail!("--simulate cannot be used with --estimate-only");
         }
 
-        let provider = Arc::new(self.provider.into_provider()?);
-        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+        // Resolved upfront so that the signer is only loaded once even when `self:` is used
+        let account = self.account.resolve(&provider).await?;
 
-        // Parses and resolves the calls
-        let calls = {
-            // TODO: show more helpful message
-            let unexpected_end_of_args = || anyhow::anyhow!("unexpected end of arguments");
+        let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()))
+            .with_self_account(SelfAccountResolver::from_resolved(&account).await?);
 
-            let mut buffer = vec![];
+        // Parses the calls from either the file or the positional arguments
+        let raw_calls = match &self.calls_file {
+            Some(calls_file) => CallsFile::load(calls_file)?.calls,
+            None => {
+                // TODO: show more helpful message
+                let unexpected_end_of_args = || anyhow::anyhow!("unexpected end of arguments");
 
-            let mut arg_iter = self.calls.into_iter();
+                let mut buffer = vec![];
 
-            while let Some(first_arg) = arg_iter.next() {
-                let contract_address = felt_decoder
-                    .decode_single_with_addr_fallback(&first_arg)
-                    .await?;
+                let mut arg_iter = self.calls.into_iter();
 
-                let next_arg = arg_iter.next().ok_or_else(unexpected_end_of_args)?;
-                let selector = felt_decoder
-                    .decode_single_with_selector_fallback(&next_arg)
-                    .await?;
+                while let Some(to) = arg_iter.next() {
+                    let selector = arg_iter.next().ok_or_else(unexpected_end_of_args)?;
 
-                let mut calldata = vec![];
-                for arg in &mut arg_iter {
-                    let mut arg = match arg.as_str() {
-                        "/" | "-" | "\\" => break,
-                        _ => felt_decoder.decode(&arg).await?,
-                    };
-                    calldata.append(&mut arg);
+                    let mut calldata = vec![];
+                    for arg in &mut arg_iter {
+                        match arg.as_str() {
+                            "/" | "-" | "\\" => break,
+                            _ => calldata.push(arg),
+                        }
+                    }
+
+                    buffer.push(RawCall {
+                        to,
+                        selector,
+                        calldata,
+                    });
                 }
 
+                buffer
+            }
+        };
+
+        // Resolves the call targets first, as the ABI file can only describe a single contract
+        let mut targets = vec![];
+        for raw_call in raw_calls.iter() {
+            let contract_address = felt_decoder
+                .decode_single_with_addr_fallback(&raw_call.to)
+                .await?;
+            let selector = felt_decoder
+                .decode_single_with_selector_fallback(&raw_call.selector)
+                .await?;
+
+            targets.push((contract_address, selector));
+        }
+
+        if self.abi.has_file()
+            && targets
+                .iter()
+                .any(|(contract_address, _)| *contract_address != targets[0].0)
+        {
+            anyhow::bail!("--abi cannot be used with calls to more than one contract");
+        }
+
+        // Resolves the calls
+        let calls = {
+            // Each contract's ABI is only loaded once even when it's called multiple times
+            let mut abis: HashMap<Felt, ContractAbi> = HashMap::new();
+            let mut buffer = vec![];
+
+            for (raw_call, (contract_address, selector)) in raw_calls.iter().zip(targets) {
+                let args = &raw_call.calldata;
+
+                let calldata = if self.abi.is_typed() {
+                    let abi = match abis.entry(contract_address) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        Entry::Vacant(entry) => entry.insert(
+                            self.abi
+                                .load_for_contract(
+                                    &provider,
+                                    BlockId::Tag(BlockTag::Pending),
+                                    contract_address,
+                                )
+                                .await?,
+                        ),
+                    };
+
+                    abi.encode_function_arguments(selector, args, &felt_decoder)
+                        .await?
+                } else {
+                    let mut calldata = vec![];
+                    for arg in args.iter() {
+                        calldata.append(&mut felt_decoder.decode(arg).await?);
+                    }
+                    calldata
+                };
+
                 buffer.push(Call {
                     to: contract_address,
                     selector,
@@ -100,7 +180,7 @@ impl Invoke {
             anyhow::bail!("empty execution");
         }
 
-        let account = self.account.into_account(provider.clone()).await?;
+        let account = account.into_account(provider.clone()).await?;
 
         let invoke_tx = match fee_setting {
             FeeSetting::Strk(fee_setting) => {
@@ -165,6 +245,8 @@ impl Invoke {
                     return Ok(());
                 }
 
+                provider.verify_chain_id().await?;
+
                 execution.send().await
             }
         }
@@ -184,7 +266,7 @@ impl Invoke {
             watch_tx(
                 &provider,
                 invoke_tx,
-                Duration::from_millis(self.poll_interval),
+                resolve_poll_interval(self.poll_interval, provider.project_config()),
             )
             .await?;
         }
@@ -22,9 +22,7 @@ use starknet::{
     macros::felt,
 };
 
-/// The default UDC address: 0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf.
-const DEFAULT_UDC_ADDRESS: Felt =
-    felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+use crate::utils::DEFAULT_UDC_ADDRESS;
 
 /// All Starknet addresses must be smaller than this bound: 2 ** 251 - 256.
 const ADDR_BOUND: [u8; 32] =
@@ -37,6 +37,24 @@ pub use parse_cairo_string::ParseCairoString;
 mod mont;
 pub use mont::Mont;
 
+mod hash;
+pub use hash::Hash;
+
+mod address;
+pub use address::Address;
+
+mod tx_hash;
+pub use tx_hash::TxHash;
+
+mod sign;
+pub use sign::Sign;
+
+mod verify;
+pub use verify::Verify;
+
+mod decode_calls;
+pub use decode_calls::DecodeCalls;
+
 mod class_by_hash;
 pub use class_by_hash::ClassByHash;
 
@@ -99,3 +117,18 @@ pub use transaction_status::TransactionStatus;
 
 mod abi;
 pub use abi::Abi;
+
+mod network;
+pub use network::Network;
+
+mod profile;
+pub use profile::Profile;
+
+mod config;
+pub use config::Config;
+
+mod address_book;
+pub use address_book::AddressBook;
+
+mod stark_name;
+pub use stark_name::StarkName;
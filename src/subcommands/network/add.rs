@@ -0,0 +1,121 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::{
+    FailoverStrategy, HttpHeader, Network, NetworkProvider, Profiles, RpcEndpoint, RpcProvider,
+};
+
+use super::{
+    resolve_chain_id, resolve_default_account, resolve_default_signer, validate_network_id,
+};
+
+#[derive(Debug, Parser)]
+pub struct Add {
+    #[clap(long, help = "Human-readable network name")]
+    name: Option<String>,
+    #[clap(
+        long,
+        required_unless_present = "fetch_chain_id",
+        help = "Chain ID in Cairo short string representation, such as SN_SEPOLIA"
+    )]
+    chain_id: Option<String>,
+    #[clap(
+        long,
+        help = "Fetch chain ID from the RPC endpoint, and check it against --chain-id if supplied"
+    )]
+    fetch_chain_id: bool,
+    #[clap(
+        long,
+        required = true,
+        help = "Starknet JSON-RPC endpoint, which may reference environment variables as \
+        ${ENV_VAR}. Can be used multiple times to add fallback endpoints"
+    )]
+    rpc: Vec<String>,
+    #[clap(
+        long = "header",
+        help = "Custom HTTP header in the NAME:VALUE format, sent to all endpoints. Can be used \
+        multiple times"
+    )]
+    headers: Vec<HttpHeader>,
+    #[clap(
+        long,
+        default_value = "priority",
+        help = "Order in which endpoints are tried"
+    )]
+    failover: FailoverStrategy,
+    #[clap(long, help = "Mark the network as an integration network")]
+    integration: bool,
+    #[clap(
+        long,
+        help = "Account config file or built-in account ID used when no account is supplied"
+    )]
+    account: Option<String>,
+    #[clap(
+        long,
+        conflicts_with = "ledger_path",
+        help = "Keystore used when no signer option is supplied"
+    )]
+    keystore: Option<String>,
+    #[clap(
+        long,
+        help = "EIP-2645 derivation path of the Ledger signer used when no signer option is \
+        supplied"
+    )]
+    ledger_path: Option<String>,
+    #[clap(help = "Network identifier")]
+    id: String,
+}
+
+impl Add {
+    pub async fn run(self, profile: &str) -> Result<()> {
+        validate_network_id(&self.id)?;
+
+        let mut profiles = Profiles::load()?;
+        let matched_profile = profiles.get_profile_mut(profile)?;
+
+        if matched_profile.networks.contains_key(&self.id) {
+            anyhow::bail!(
+                "network `{}` already exists in profile `{}`",
+                self.id,
+                profile
+            );
+        }
+
+        let provider = NetworkProvider::Rpc(RpcProvider {
+            endpoints: self
+                .rpc
+                .into_iter()
+                .map(|url| RpcEndpoint::new(url, self.headers.clone()))
+                .collect::<Result<_>>()?,
+            failover: self.failover,
+        });
+
+        // Unwrapping is safe as `clap` requires one of --chain-id and --fetch-chain-id
+        let chain_id = resolve_chain_id(self.chain_id.as_deref(), self.fetch_chain_id, &provider)
+            .await?
+            .unwrap();
+
+        matched_profile.networks.insert(
+            self.id.clone(),
+            Network {
+                name: self.name,
+                chain_id,
+                is_integration: self.integration,
+                provider,
+                account: self.account.map(resolve_default_account),
+                signer: resolve_default_signer(self.keystore, self.ledger_path)?,
+            },
+        );
+
+        profiles.save()?;
+
+        eprintln!(
+            "Network {} added to profile {}",
+            format!("`{}`", self.id).bright_yellow(),
+            format!("`{profile}`").bright_yellow()
+        );
+
+        Ok(())
+    }
+}
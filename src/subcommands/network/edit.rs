@@ -0,0 +1,191 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::{
+    FailoverStrategy, HttpHeader, NetworkProvider, Profiles, RpcEndpoint, RpcProvider,
+};
+
+use super::{resolve_chain_id, resolve_default_account, resolve_default_signer};
+
+#[derive(Debug, Parser)]
+pub struct Edit {
+    #[clap(
+        long,
+        conflicts_with = "clear_name",
+        help = "New human-readable network name"
+    )]
+    name: Option<String>,
+    #[clap(long, help = "Remove the human-readable network name")]
+    clear_name: bool,
+    #[clap(
+        long,
+        help = "New chain ID in Cairo short string representation, such as SN_SEPOLIA"
+    )]
+    chain_id: Option<String>,
+    #[clap(
+        long,
+        help = "Fetch chain ID from the RPC endpoint, and check it against --chain-id if supplied"
+    )]
+    fetch_chain_id: bool,
+    #[clap(
+        long,
+        help = "Replace Starknet JSON-RPC endpoints, which may reference environment variables \
+        as ${ENV_VAR}. Can be used multiple times to set fallback endpoints"
+    )]
+    rpc: Vec<String>,
+    #[clap(
+        long = "header",
+        conflicts_with = "clear_headers",
+        help = "Replace custom HTTP headers of all endpoints with ones in the NAME:VALUE format. \
+        Can be used multiple times"
+    )]
+    headers: Vec<HttpHeader>,
+    #[clap(long, help = "Remove all custom HTTP headers")]
+    clear_headers: bool,
+    #[clap(long, help = "Order in which endpoints are tried")]
+    failover: Option<FailoverStrategy>,
+    #[clap(long, help = "Whether the network is an integration network")]
+    integration: Option<bool>,
+    #[clap(
+        long,
+        conflicts_with = "clear_account",
+        help = "New account config file or built-in account ID used when no account is supplied"
+    )]
+    account: Option<String>,
+    #[clap(long, help = "Remove the default account")]
+    clear_account: bool,
+    #[clap(
+        long,
+        conflicts_with_all = ["ledger_path", "clear_signer"],
+        help = "New keystore used when no signer option is supplied"
+    )]
+    keystore: Option<String>,
+    #[clap(
+        long,
+        conflicts_with = "clear_signer",
+        help = "New EIP-2645 derivation path of the Ledger signer used when no signer option is \
+        supplied"
+    )]
+    ledger_path: Option<String>,
+    #[clap(long, help = "Remove the default signer")]
+    clear_signer: bool,
+    #[clap(help = "Network identifier")]
+    id: String,
+}
+
+impl Edit {
+    pub async fn run(self, profile: &str) -> Result<()> {
+        let mut profiles = Profiles::load()?;
+
+        let network = profiles
+            .profiles
+            .get_mut(profile)
+            .and_then(|matched_profile| matched_profile.networks.get_mut(&self.id))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "network `{}` is not configured in profile `{}`",
+                    self.id,
+                    profile
+                )
+            })?;
+
+        if !self.rpc.is_empty() {
+            network.provider = match &network.provider {
+                // Keeps existing headers of endpoints at the same positions when only URLs are
+                // changed
+                NetworkProvider::Rpc(rpc) => NetworkProvider::Rpc(RpcProvider {
+                    endpoints: self
+                        .rpc
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ind, url)| {
+                            RpcEndpoint::new(
+                                url,
+                                rpc.endpoints
+                                    .get(ind)
+                                    .map(|endpoint| endpoint.headers.clone())
+                                    .unwrap_or_default(),
+                            )
+                        })
+                        .collect::<Result<_>>()?,
+                    failover: rpc.failover,
+                }),
+                NetworkProvider::Free(_) => NetworkProvider::Rpc(RpcProvider {
+                    endpoints: self
+                        .rpc
+                        .into_iter()
+                        .map(|url| RpcEndpoint::new(url, vec![]))
+                        .collect::<Result<_>>()?,
+                    failover: FailoverStrategy::Priority,
+                }),
+            };
+        }
+
+        if !self.headers.is_empty() || self.clear_headers {
+            match &mut network.provider {
+                NetworkProvider::Rpc(rpc) => {
+                    for endpoint in rpc.endpoints.iter_mut() {
+                        endpoint.headers = self.headers.clone();
+                    }
+                }
+                NetworkProvider::Free(_) => {
+                    anyhow::bail!("custom HTTP headers can only be used with RPC providers")
+                }
+            }
+        }
+
+        if let Some(failover) = self.failover {
+            match &mut network.provider {
+                NetworkProvider::Rpc(rpc) => {
+                    rpc.failover = failover;
+                }
+                NetworkProvider::Free(_) => {
+                    anyhow::bail!("--failover can only be used with RPC providers")
+                }
+            }
+        }
+
+        if let Some(chain_id) = resolve_chain_id(
+            self.chain_id.as_deref(),
+            self.fetch_chain_id,
+            &network.provider,
+        )
+        .await?
+        {
+            network.chain_id = chain_id;
+        }
+
+        if let Some(name) = self.name {
+            network.name = Some(name);
+        } else if self.clear_name {
+            network.name = None;
+        }
+
+        if let Some(integration) = self.integration {
+            network.is_integration = integration;
+        }
+
+        if let Some(account) = self.account {
+            network.account = Some(resolve_default_account(account));
+        } else if self.clear_account {
+            network.account = None;
+        }
+
+        if let Some(signer) = resolve_default_signer(self.keystore, self.ledger_path)? {
+            network.signer = Some(signer);
+        } else if self.clear_signer {
+            network.signer = None;
+        }
+
+        profiles.save()?;
+
+        eprintln!(
+            "Network {} updated in profile {}",
+            format!("`{}`", self.id).bright_yellow(),
+            format!("`{profile}`").bright_yellow()
+        );
+
+        Ok(())
+    }
+}
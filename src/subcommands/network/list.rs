@@ -0,0 +1,33 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use starknet::core::utils::parse_cairo_short_string;
+
+use crate::profile::{Profiles, DEFAULT_PROFILE_NAME};
+
+#[derive(Debug, Parser)]
+pub struct List {}
+
+impl List {
+    pub fn run(self, profile: &str) -> Result<()> {
+        let profiles = Profiles::load()?;
+
+        let networks = match profiles.profiles.get(profile) {
+            Some(matched_profile) => &matched_profile.networks,
+            // An absent `default` profile is simply an empty one
+            None if profile == DEFAULT_PROFILE_NAME => return Ok(()),
+            None => anyhow::bail!("profile `{}` is not defined", profile),
+        };
+
+        for (id, network) in networks.iter() {
+            println!(
+                "{} ({}): {}",
+                id.bright_yellow(),
+                parse_cairo_short_string(&network.chain_id)?,
+                network.provider
+            );
+        }
+
+        Ok(())
+    }
+}
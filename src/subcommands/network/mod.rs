@@ -0,0 +1,212 @@
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use starknet::{
+    core::{
+        types::Felt,
+        utils::{cairo_short_string_to_felt, parse_cairo_short_string},
+    },
+    providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider},
+};
+
+use crate::{
+    account::find_builtin_account,
+    hd_path::Eip2645Path,
+    profile::{NetworkProvider, NetworkSigner, ProfileArgs, RpcEndpoint},
+};
+
+mod add;
+use add::Add;
+
+mod list;
+use list::List;
+
+mod show;
+use show::Show;
+
+mod edit;
+use edit::Edit;
+
+mod rename;
+use rename::Rename;
+
+mod remove;
+use remove::Remove;
+
+#[derive(Debug, Parser)]
+pub struct Network {
+    #[clap(flatten)]
+    profile: ProfileArgs,
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    #[clap(about = "Add a new network to a profile")]
+    Add(Add),
+    #[clap(about = "List all networks in a profile")]
+    List(List),
+    #[clap(about = "Show the configuration of a network")]
+    Show(Show),
+    #[clap(about = "Modify an existing network")]
+    Edit(Edit),
+    #[clap(about = "Change the identifier of an existing network")]
+    Rename(Rename),
+    #[clap(about = "Remove a network from a profile")]
+    Remove(Remove),
+}
+
+impl Network {
+    pub async fn run(self) -> Result<()> {
+        let profile = self.profile.name();
+
+        match self.command {
+            Subcommands::Add(cmd) => cmd.run(profile).await,
+            Subcommands::List(cmd) => cmd.run(profile),
+            Subcommands::Show(cmd) => cmd.run(profile),
+            Subcommands::Edit(cmd) => cmd.run(profile).await,
+            Subcommands::Rename(cmd) => cmd.run(profile),
+            Subcommands::Remove(cmd) => cmd.run(profile),
+        }
+    }
+}
+
+/// Network identifiers end up as TOML keys and command line values, so we keep them simple.
+fn validate_network_id(id: &str) -> Result<()> {
+    if id.is_empty() {
+        anyhow::bail!("network identifier must not be empty");
+    }
+
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!(
+            "invalid network identifier \"{}\": only ASCII letters, digits, `-` and `_` are \
+            allowed",
+            id
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetches the chain ID served by an RPC endpoint, using the same headers that would be sent when
+/// the network is actually used.
+async fn fetch_chain_id(endpoint: &RpcEndpoint) -> Result<Felt> {
+    let expanded = endpoint.expand()?;
+
+    let mut transport = HttpTransport::new(expanded.url);
+    for header in expanded.headers.into_iter() {
+        transport.add_header(header.name, header.value);
+    }
+
+    JsonRpcClient::new(transport)
+        .chain_id()
+        .await
+        .map_err(|err| anyhow::anyhow!("unable to fetch chain ID from {}: {}", endpoint.url, err))
+}
+
+/// Checks the chain ID supplied by the user against the one served by the endpoint, if requested.
+async fn resolve_chain_id(
+    chain_id: Option<&str>,
+    fetch: bool,
+    provider: &NetworkProvider,
+) -> Result<Option<Felt>> {
+    let chain_id = chain_id
+        .map(|chain_id| {
+            cairo_short_string_to_felt(chain_id)
+                .map_err(|_| anyhow::anyhow!("invalid chain ID \"{}\"", chain_id))
+        })
+        .transpose()?;
+
+    if !fetch {
+        return Ok(chain_id);
+    }
+
+    let rpc = match provider {
+        NetworkProvider::Rpc(rpc) => rpc,
+        NetworkProvider::Free(_) => {
+            anyhow::bail!("--fetch-chain-id can only be used with RPC providers")
+        }
+    };
+
+    // All endpoints must serve the same chain for failover to be safe
+    let mut fetched_chain_id = None;
+    for endpoint in rpc.endpoints.iter() {
+        let endpoint_chain_id = fetch_chain_id(endpoint).await?;
+        match fetched_chain_id {
+            Some(fetched_chain_id) if fetched_chain_id != endpoint_chain_id => {
+                anyhow::bail!(
+                    "chain ID mismatch: endpoint {} serves {}, while previous endpoints serve {}",
+                    endpoint.url,
+                    parse_cairo_short_string(&endpoint_chain_id)?,
+                    parse_cairo_short_string(&fetched_chain_id)?
+                );
+            }
+            _ => fetched_chain_id = Some(endpoint_chain_id),
+        }
+    }
+
+    // `RpcProvider` always contains at least one endpoint
+    let fetched_chain_id = fetched_chain_id.unwrap();
+    match chain_id {
+        Some(chain_id) if chain_id != fetched_chain_id => Err(anyhow::anyhow!(
+            "chain ID mismatch: expected {}, but the endpoint serves {}",
+            parse_cairo_short_string(&chain_id)?,
+            parse_cairo_short_string(&fetched_chain_id)?
+        )),
+        _ => {
+            eprintln!(
+                "Chain ID fetched from endpoint: {}",
+                parse_cairo_short_string(&fetched_chain_id)?.bright_yellow()
+            );
+
+            Ok(Some(fetched_chain_id))
+        }
+    }
+}
+
+/// Default accounts are used from any working directory, so relative paths are made absolute.
+/// Built-in account IDs are kept as is.
+fn resolve_default_account(account: String) -> String {
+    if find_builtin_account(&account).is_some() {
+        account
+    } else {
+        absolute_path(account)
+    }
+}
+
+fn resolve_default_signer(
+    keystore: Option<String>,
+    ledger_path: Option<String>,
+) -> Result<Option<NetworkSigner>> {
+    Ok(match (keystore, ledger_path) {
+        (Some(keystore), None) => Some(NetworkSigner::Keystore {
+            path: absolute_path(keystore),
+        }),
+        (None, Some(ledger_path)) => {
+            // Validated here to fail early instead of when the network is used
+            Eip2645Path::from_str(&ledger_path)?;
+            Some(NetworkSigner::Ledger { path: ledger_path })
+        }
+        (None, None) => None,
+        // Rejected by `clap`
+        (Some(_), Some(_)) => unreachable!(),
+    })
+}
+
+fn absolute_path(path: String) -> String {
+    let expanded = PathBuf::from(shellexpand::tilde(&path).into_owned());
+    if expanded.is_absolute() {
+        return path;
+    }
+
+    match std::env::current_dir() {
+        Ok(current_dir) => current_dir.join(expanded).to_string_lossy().into_owned(),
+        Err(_) => path,
+    }
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::Profiles;
+
+#[derive(Debug, Parser)]
+pub struct Remove {
+    #[clap(help = "Network identifier")]
+    id: String,
+}
+
+impl Remove {
+    pub fn run(self, profile: &str) -> Result<()> {
+        let mut profiles = Profiles::load()?;
+
+        let removed = profiles
+            .profiles
+            .get_mut(profile)
+            .and_then(|matched_profile| matched_profile.networks.shift_remove(&self.id));
+
+        if removed.is_none() {
+            anyhow::bail!(
+                "network `{}` is not configured in profile `{}`",
+                self.id,
+                profile
+            );
+        }
+
+        profiles.save()?;
+
+        eprintln!(
+            "Network {} removed from profile {}",
+            format!("`{}`", self.id).bright_yellow(),
+            format!("`{profile}`").bright_yellow()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::Profiles;
+
+use super::validate_network_id;
+
+#[derive(Debug, Parser)]
+pub struct Rename {
+    #[clap(help = "Current network identifier")]
+    old_id: String,
+    #[clap(help = "New network identifier")]
+    new_id: String,
+}
+
+impl Rename {
+    pub fn run(self, profile: &str) -> Result<()> {
+        validate_network_id(&self.new_id)?;
+
+        let mut profiles = Profiles::load()?;
+
+        let networks = &mut profiles
+            .profiles
+            .get_mut(profile)
+            .ok_or_else(|| anyhow::anyhow!("profile `{}` is not defined", profile))?
+            .networks;
+
+        if networks.contains_key(&self.new_id) {
+            anyhow::bail!(
+                "network `{}` already exists in profile `{}`",
+                self.new_id,
+                profile
+            );
+        }
+
+        let index = networks.get_index_of(&self.old_id).ok_or_else(|| {
+            anyhow::anyhow!(
+                "network `{}` is not configured in profile `{}`",
+                self.old_id,
+                profile
+            )
+        })?;
+
+        // Renaming in place preserves the ordering in the profiles file
+        let (_, network) = networks.swap_remove_index(index).unwrap();
+        let (new_index, _) = networks.insert_full(self.new_id.clone(), network);
+        networks.swap_indices(index, new_index);
+
+        profiles.save()?;
+
+        eprintln!(
+            "Network {} renamed to {}",
+            format!("`{}`", self.old_id).bright_yellow(),
+            format!("`{}`", self.new_id).bright_yellow()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+use clap::Parser;
+
+use crate::profile::Profiles;
+
+#[derive(Debug, Parser)]
+pub struct Show {
+    #[clap(help = "Network identifier")]
+    id: String,
+}
+
+impl Show {
+    pub fn run(self, profile: &str) -> Result<()> {
+        let profiles = Profiles::load()?;
+
+        let network = profiles
+            .profiles
+            .get(profile)
+            .and_then(|matched_profile| matched_profile.networks.get(&self.id))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "network `{}` is not configured in profile `{}`",
+                    self.id,
+                    profile
+                )
+            })?;
+
+        print!("{}", toml::to_string_pretty(network)?);
+
+        Ok(())
+    }
+}
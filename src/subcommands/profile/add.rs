@@ -0,0 +1,38 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::{Profile, Profiles};
+
+#[derive(Debug, Parser)]
+pub struct Add {
+    #[clap(help = "Profile name")]
+    name: String,
+}
+
+impl Add {
+    pub fn run(self) -> Result<()> {
+        if self.name.is_empty() {
+            anyhow::bail!("profile name must not be empty");
+        }
+
+        let mut profiles = Profiles::load()?;
+
+        if profiles.profiles.contains_key(&self.name) {
+            anyhow::bail!("profile `{}` already exists", self.name);
+        }
+
+        profiles
+            .profiles
+            .insert(self.name.clone(), Profile::default());
+
+        profiles.save()?;
+
+        eprintln!(
+            "Profile {} created",
+            format!("`{}`", self.name).bright_yellow()
+        );
+
+        Ok(())
+    }
+}
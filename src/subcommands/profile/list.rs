@@ -0,0 +1,24 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::Profiles;
+
+#[derive(Debug, Parser)]
+pub struct List {}
+
+impl List {
+    pub fn run(self) -> Result<()> {
+        let profiles = Profiles::load()?;
+
+        for (name, profile) in profiles.profiles.iter() {
+            println!(
+                "{} ({} networks)",
+                name.bright_yellow(),
+                profile.networks.len()
+            );
+        }
+
+        Ok(())
+    }
+}
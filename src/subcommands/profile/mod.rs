@@ -0,0 +1,37 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod list;
+use list::List;
+
+mod add;
+use add::Add;
+
+mod remove;
+use remove::Remove;
+
+#[derive(Debug, Parser)]
+pub struct Profile {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    #[clap(about = "List all profiles")]
+    List(List),
+    #[clap(about = "Create a new empty profile")]
+    Add(Add),
+    #[clap(about = "Remove a profile along with all its networks")]
+    Remove(Remove),
+}
+
+impl Profile {
+    pub fn run(self) -> Result<()> {
+        match self.command {
+            Subcommands::List(cmd) => cmd.run(),
+            Subcommands::Add(cmd) => cmd.run(),
+            Subcommands::Remove(cmd) => cmd.run(),
+        }
+    }
+}
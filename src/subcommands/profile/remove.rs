@@ -0,0 +1,30 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+
+use crate::profile::Profiles;
+
+#[derive(Debug, Parser)]
+pub struct Remove {
+    #[clap(help = "Profile name")]
+    name: String,
+}
+
+impl Remove {
+    pub fn run(self) -> Result<()> {
+        let mut profiles = Profiles::load()?;
+
+        if profiles.profiles.shift_remove(&self.name).is_none() {
+            anyhow::bail!("profile `{}` is not defined", self.name);
+        }
+
+        profiles.save()?;
+
+        eprintln!(
+            "Profile {} removed",
+            format!("`{}`", self.name).bright_yellow()
+        );
+
+        Ok(())
+    }
+}
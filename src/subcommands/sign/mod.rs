@@ -0,0 +1,25 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod typed_data;
+use typed_data::TypedData;
+
+#[derive(Debug, Parser)]
+pub struct Sign {
+    #[clap(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    #[clap(about = "Sign SNIP-12 typed data with an account's signer")]
+    TypedData(TypedData),
+}
+
+impl Sign {
+    pub async fn run(self) -> Result<()> {
+        match self.command {
+            Subcommands::TypedData(cmd) => cmd.run().await,
+        }
+    }
+}
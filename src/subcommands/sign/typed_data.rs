@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use starknet::signers::{Signer, SignerInteractivityContext};
+
+use crate::{
+    account::AccountArgs, path::ExpandedPathbufParser, utils::print_colored_json,
+    verbosity::VerbosityArgs, ProviderArgs,
+};
+
+#[derive(Debug, Parser)]
+pub struct TypedData {
+    #[clap(flatten)]
+    provider: ProviderArgs,
+    #[clap(flatten)]
+    account: AccountArgs,
+    #[clap(
+        value_parser = ExpandedPathbufParser,
+        help = "Path to the SNIP-12 typed data JSON file (revision 0 or 1)"
+    )]
+    file: PathBuf,
+    #[clap(flatten)]
+    verbosity: VerbosityArgs,
+}
+
+impl TypedData {
+    pub async fn run(self) -> Result<()> {
+        self.verbosity.setup_logging();
+
+        let typed_data: starknet::core::types::TypedData =
+            serde_json::from_reader(std::fs::File::open(&self.file)?).map_err(|err| {
+                anyhow::anyhow!("invalid typed data {}: {}", self.file.display(), err)
+            })?;
+
+        // The provider is only used for resolving account and signer defaults of the network
+        let provider = self.provider.into_provider()?;
+        let (account_address, signer) = self.account.into_address_and_signer(&provider).await?;
+
+        let message_hash = typed_data.message_hash(account_address)?;
+        eprintln!(
+            "Signing message hash {} for account {}",
+            format!("{message_hash:#064x}").bright_yellow(),
+            format!("{account_address:#064x}").bright_yellow()
+        );
+
+        if signer.is_interactive(SignerInteractivityContext::Other) {
+            eprintln!("Please confirm the signing operation on your signer");
+        }
+
+        let signature = signer.sign_hash(&message_hash).await?;
+        print_colored_json(&vec![signature.r, signature.s])?;
+
+        Ok(())
+    }
+}
@@ -12,6 +12,12 @@ use ledger::Ledger;
 mod gen_keypair;
 use gen_keypair::GenKeypair;
 
+mod sign_hash;
+use sign_hash::SignHash;
+
+mod verify_hash;
+use verify_hash::VerifyHash;
+
 #[derive(Debug, Parser)]
 pub struct Signer {
     #[clap(subcommand)]
@@ -27,6 +33,10 @@ enum Subcommands {
     Ledger(Ledger),
     #[clap(about = "Randomly generate a new key pair")]
     GenKeypair(GenKeypair),
+    #[clap(about = "Sign a raw hash with any signer")]
+    SignHash(SignHash),
+    #[clap(about = "Verify a signature of a raw hash against a public key")]
+    VerifyHash(VerifyHash),
 }
 
 impl Signer {
@@ -36,6 +46,8 @@ impl Signer {
             #[cfg(feature = "ledger")]
             Subcommands::Ledger(cmd) => cmd.run().await,
             Subcommands::GenKeypair(cmd) => cmd.run(),
+            Subcommands::SignHash(cmd) => cmd.run().await,
+            Subcommands::VerifyHash(cmd) => cmd.run(),
         }
     }
 }
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use serde::Serialize;
+use serde_with::serde_as;
+use starknet::{
+    core::{serde::unsigned_field_element::UfeHex, types::Felt},
+    signers::{Signer, SignerInteractivityContext},
+};
+
+use crate::{config::ProjectConfig, signer::SignerArgs, utils::print_colored_json};
+
+#[derive(Debug, Parser)]
+pub struct SignHash {
+    #[clap(flatten)]
+    signer: SignerArgs,
+    #[clap(long, help = "Print the signature as JSON")]
+    json: bool,
+    #[clap(long, help = "Also print the public key of the signer")]
+    public_key: bool,
+    #[clap(help = "The raw hash to be signed")]
+    hash: String,
+}
+
+#[serde_as]
+#[derive(Serialize)]
+struct SignatureOutput {
+    #[serde_as(as = "UfeHex")]
+    r: Felt,
+    #[serde_as(as = "UfeHex")]
+    s: Felt,
+    #[serde_as(as = "Option<UfeHex>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_key: Option<Felt>,
+}
+
+impl SignHash {
+    pub async fn run(self) -> Result<()> {
+        let hash = Felt::from_hex(&self.hash)?;
+
+        let signer = self.signer.into_signer(ProjectConfig::load()?).await?;
+
+        let public_key = if self.public_key {
+            Some(signer.get_public_key().await?.scalar())
+        } else {
+            None
+        };
+
+        if signer.is_interactive(SignerInteractivityContext::Other) {
+            eprintln!(
+                "{}",
+                "WARNING: blind signing a raw hash could be dangerous. Make sure you ONLY sign \
+                hashes from trusted sources."
+                    .bright_magenta()
+            );
+            eprintln!("Please confirm the signing operation on your Ledger");
+        }
+
+        let signature = signer.sign_hash(&hash).await?;
+
+        if self.json {
+            print_colored_json(&SignatureOutput {
+                r: signature.r,
+                s: signature.s,
+                public_key,
+            })?;
+        } else {
+            println!("r          : {:#064x}", signature.r);
+            println!("s          : {:#064x}", signature.s);
+            if let Some(public_key) = public_key {
+                println!("Public key : {:#064x}", public_key);
+            }
+        }
+
+        Ok(())
+    }
+}
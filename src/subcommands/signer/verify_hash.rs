@@ -0,0 +1,32 @@
+use anyhow::Result;
+use clap::Parser;
+use starknet::core::types::Felt;
+
+#[derive(Debug, Parser)]
+pub struct VerifyHash {
+    #[clap(long, help = "Public key of the signer")]
+    public_key: String,
+    #[clap(help = "The raw hash that was signed")]
+    hash: String,
+    #[clap(help = "The r value of the signature")]
+    r: String,
+    #[clap(help = "The s value of the signature")]
+    s: String,
+}
+
+impl VerifyHash {
+    pub fn run(self) -> Result<()> {
+        let public_key = Felt::from_hex(&self.public_key)?;
+        let hash = Felt::from_hex(&self.hash)?;
+        let r = Felt::from_hex(&self.r)?;
+        let s = Felt::from_hex(&self.s)?;
+
+        if !starknet_crypto::verify(&public_key, &hash, &r, &s)? {
+            anyhow::bail!("signature is NOT valid");
+        }
+
+        eprintln!("Signature is valid");
+
+        Ok(())
+    }
+}
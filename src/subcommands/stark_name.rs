@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use clap::Parser;
+
+use crate::{
+    address_book::AddressBookResolver, decode::FeltDecoder, verbosity::VerbosityArgs, ProviderArgs,
+};
+
+#[derive(Debug, Parser)]
+pub struct StarkName {
+    #[clap(flatten)]
+    provider: ProviderArgs,
+    #[clap(help = "Contract address")]
+    address: String,
+    #[clap(flatten)]
+    verbosity: VerbosityArgs,
+}
+
+impl StarkName {
+    pub async fn run(self) -> Result<()> {
+        self.verbosity.setup_logging();
+
+        let provider = Arc::new(self.provider.into_provider()?);
+        let resolver = AddressBookResolver::new(provider.clone());
+
+        let address = FeltDecoder::new(AddressBookResolver::new(provider))
+            .decode_single_with_addr_fallback(&self.address)
+            .await?;
+
+        match resolver.lookup_stark_name(address).await? {
+            Some(domain) => println!("{domain}"),
+            None => anyhow::bail!("no Starknet ID domain found for address {:#064x}", address),
+        }
+
+        Ok(())
+    }
+}
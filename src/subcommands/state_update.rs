@@ -3,7 +3,11 @@ use clap::Parser;
 use starknet::{core::types::BlockId, providers::Provider};
 
 use crate::{
-    block_id::BlockIdParser, utils::print_colored_json, verbosity::VerbosityArgs, ProviderArgs,
+    address_book::address_labels,
+    block_id::BlockIdParser,
+    utils::{print_colored_json, print_labelled_json},
+    verbosity::VerbosityArgs,
+    ProviderArgs,
 };
 
 #[derive(Debug, Parser)]
@@ -16,6 +20,11 @@ pub struct StateUpdate {
         help = "Block number, hash, or tag (latest/pending)"
     )]
     block_id: BlockId,
+    #[clap(
+        long,
+        help = "Annotate known addresses with names from the address book and built-in accounts"
+    )]
+    label: bool,
     #[clap(flatten)]
     verbosity: VerbosityArgs,
 }
@@ -26,7 +35,13 @@ impl StateUpdate {
 
         let provider = self.provider.into_provider()?;
 
-        print_colored_json(&provider.get_state_update(self.block_id).await?)?;
+        let state_update = provider.get_state_update(self.block_id).await?;
+        if self.label {
+            let labels = address_labels(provider.chain_id().await?, provider.project_config())?;
+            print_labelled_json(&state_update, &labels)?;
+        } else {
+            print_colored_json(&state_update)?;
+        }
 
         Ok(())
     }
@@ -1,8 +1,15 @@
 use anyhow::Result;
 use clap::Parser;
+use colored::Colorize;
 use starknet::{core::types::Felt, providers::Provider};
 
-use crate::{utils::print_colored_json, verbosity::VerbosityArgs, ProviderArgs};
+use crate::{
+    address_book::address_labels,
+    tx_hash::TransactionBody,
+    utils::{print_colored_json, print_labelled_json},
+    verbosity::VerbosityArgs,
+    ProviderArgs,
+};
 
 #[derive(Debug, Parser)]
 pub struct Transaction {
@@ -10,6 +17,16 @@ pub struct Transaction {
     provider: ProviderArgs,
     #[clap(help = "Transaction hash")]
     hash: String,
+    #[clap(
+        long,
+        help = "Annotate known addresses with names from the address book and built-in accounts"
+    )]
+    label: bool,
+    #[clap(
+        long,
+        help = "Recompute the hash of the transaction and fail if it does not match (v3 only)"
+    )]
+    verify_hash: bool,
     #[clap(flatten)]
     verbosity: VerbosityArgs,
 }
@@ -22,7 +39,35 @@ impl Transaction {
         let transaction_hash = Felt::from_hex(&self.hash)?;
 
         let transaction = provider.get_transaction_by_hash(transaction_hash).await?;
-        print_colored_json(&transaction)?;
+        if self.label {
+            let labels = address_labels(provider.chain_id().await?, provider.project_config())?;
+            print_labelled_json(&transaction, &labels)?;
+        } else {
+            print_colored_json(&transaction)?;
+        }
+
+        if self.verify_hash {
+            let chain_id = provider.chain_id().await?;
+            let body = TransactionBody::from_json(serde_json::to_value(&transaction)?)?;
+
+            let computed_hash = body.hash(chain_id, true)?;
+            let matches = computed_hash == transaction_hash
+                || (body.may_omit_l1_data_gas() && body.hash(chain_id, false)? == transaction_hash);
+
+            if !matches {
+                anyhow::bail!(
+                    "transaction hash mismatch: requested {:#064x} but the returned transaction \
+                    hashes to {:#064x}",
+                    transaction_hash,
+                    computed_hash
+                );
+            }
+
+            eprintln!(
+                "Transaction hash {} verified",
+                format!("{transaction_hash:#064x}").bright_yellow()
+            );
+        }
 
         Ok(())
     }
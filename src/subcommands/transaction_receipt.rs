@@ -2,7 +2,12 @@ use anyhow::Result;
 use clap::Parser;
 use starknet::{core::types::Felt, providers::Provider};
 
-use crate::{utils::print_colored_json, verbosity::VerbosityArgs, ProviderArgs};
+use crate::{
+    address_book::address_labels,
+    utils::{print_colored_json, print_labelled_json},
+    verbosity::VerbosityArgs,
+    ProviderArgs,
+};
 
 #[derive(Debug, Parser)]
 pub struct TransactionReceipt {
@@ -10,6 +15,11 @@ pub struct TransactionReceipt {
     provider: ProviderArgs,
     #[clap(help = "Transaction hash")]
     hash: String,
+    #[clap(
+        long,
+        help = "Annotate known addresses with names from the address book and built-in accounts"
+    )]
+    label: bool,
     #[clap(flatten)]
     verbosity: VerbosityArgs,
 }
@@ -22,7 +32,12 @@ impl TransactionReceipt {
         let transaction_hash = Felt::from_hex(&self.hash)?;
 
         let receipt = provider.get_transaction_receipt(transaction_hash).await?;
-        print_colored_json(&receipt)?;
+        if self.label {
+            let labels = address_labels(provider.chain_id().await?, provider.project_config())?;
+            print_labelled_json(&receipt, &labels)?;
+        } else {
+            print_colored_json(&receipt)?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,48 @@
+use std::{io::Read, path::PathBuf};
+
+use anyhow::Result;
+use clap::Parser;
+use starknet::core::utils::cairo_short_string_to_felt;
+
+use crate::{path::ExpandedPathbufParser, tx_hash::TransactionBody};
+
+#[derive(Debug, Parser)]
+pub struct TxHash {
+    #[clap(
+        long,
+        help = "Chain ID the transaction is meant for, such as SN_MAIN or SN_SEPOLIA"
+    )]
+    chain_id: String,
+    #[clap(
+        long,
+        help = "Compute the hash without L1 data gas bounds, as done before Starknet v0.13.4"
+    )]
+    legacy: bool,
+    #[clap(
+        value_parser = ExpandedPathbufParser,
+        help = "Path to the v3 transaction in JSON-RPC format, or `-` for stdin"
+    )]
+    file: PathBuf,
+}
+
+impl TxHash {
+    pub fn run(self) -> Result<()> {
+        let chain_id = cairo_short_string_to_felt(&self.chain_id)
+            .map_err(|_| anyhow::anyhow!("invalid chain ID \"{}\"", self.chain_id))?;
+
+        let content = if self.file == PathBuf::from("-") {
+            let mut content = String::new();
+            std::io::stdin().read_to_string(&mut content)?;
+            content
+        } else {
+            std::fs::read_to_string(&self.file)?
+        };
+
+        let transaction = TransactionBody::from_json(serde_json::from_str(&content)?)?;
+        let transaction_hash = transaction.hash(chain_id, !self.legacy)?;
+
+        println!("{transaction_hash:#064x}");
+
+        Ok(())
+    }
+}
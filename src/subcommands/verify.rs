@@ -0,0 +1,177 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use clap::Parser;
+use colored::Colorize;
+use starknet::{
+    core::{
+        crypto::Signature,
+        types::{BlockId, BlockTag, Felt, FunctionCall, StarknetError},
+    },
+    macros::{selector, short_string},
+    providers::{Provider, ProviderError},
+    signers::VerifyingKey,
+};
+
+use crate::{
+    address_book::AddressBookResolver,
+    chain_id::{ChainIdSource, OfflineSource},
+    decode::FeltDecoder,
+    error::provider_error_mapper,
+    path::ExpandedPathbufParser,
+    starknet_id::ContractCaller,
+    verbosity::VerbosityArgs,
+    ProviderArgs,
+};
+
+/// Returned by `is_valid_signature` of SNIP-6 accounts.
+const VALID: Felt = short_string!("VALID");
+
+#[derive(Debug, Parser)]
+pub struct Verify {
+    #[clap(flatten)]
+    provider: ProviderArgs,
+    #[clap(
+        long,
+        value_parser = ExpandedPathbufParser,
+        conflicts_with = "hash",
+        required_unless_present = "hash",
+        help = "Path to the SNIP-12 typed data JSON file that was signed"
+    )]
+    typed_data: Option<PathBuf>,
+    #[clap(long, help = "Raw hash that was signed")]
+    hash: Option<String>,
+    #[clap(
+        long,
+        help = "Account address. Required for typed data and on-chain verification"
+    )]
+    address: Option<String>,
+    #[clap(
+        long,
+        help = "Verify the signature locally against this public key instead of calling the \
+        account's `is_valid_signature`"
+    )]
+    public_key: Option<String>,
+    #[clap(required = true, help = "Signature elements")]
+    signature: Vec<String>,
+    #[clap(flatten)]
+    verbosity: VerbosityArgs,
+}
+
+impl Verify {
+    pub async fn run(self) -> Result<()> {
+        self.verbosity.setup_logging();
+
+        let (hash, valid) = match &self.public_key {
+            // Local verification never needs network access
+            Some(public_key) => {
+                let felt_decoder = FeltDecoder::new(AddressBookResolver::new(OfflineSource));
+                let (_, hash, signature) = self.decode_hash_and_signature(&felt_decoder).await?;
+                let public_key = felt_decoder.decode_single(public_key).await?;
+
+                let (r, s) = match signature[..] {
+                    [r, s] => (r, s),
+                    _ => anyhow::bail!(
+                        "local verification expects a signature of 2 elements but found {}",
+                        signature.len()
+                    ),
+                };
+
+                (
+                    hash,
+                    VerifyingKey::from_scalar(public_key).verify(&hash, &Signature { r, s })?,
+                )
+            }
+            None => {
+                let provider = Arc::new(self.provider.clone().into_provider()?);
+                let felt_decoder = FeltDecoder::new(AddressBookResolver::new(provider.clone()));
+                let (address, hash, signature) =
+                    self.decode_hash_and_signature(&felt_decoder).await?;
+
+                let address = address.ok_or_else(|| {
+                    anyhow::anyhow!("--address is required for on-chain verification")
+                })?;
+
+                let mut calldata = vec![hash, Felt::from(signature.len())];
+                calldata.extend_from_slice(&signature);
+
+                let valid = match provider
+                    .call(
+                        FunctionCall {
+                            contract_address: address,
+                            entry_point_selector: selector!("is_valid_signature"),
+                            calldata,
+                        },
+                        BlockId::Tag(BlockTag::Pending),
+                    )
+                    .await
+                {
+                    // Legacy accounts return `1` instead of `VALID`
+                    Ok(result) => result
+                        .first()
+                        .is_some_and(|value| *value == VALID || *value == Felt::ONE),
+                    // Many accounts assert on invalid signatures instead of returning zero
+                    Err(ProviderError::StarknetError(StarknetError::ContractError(_))) => false,
+                    Err(err) => return Err(provider_error_mapper(err)),
+                };
+
+                (hash, valid)
+            }
+        };
+
+        if !valid {
+            anyhow::bail!("signature is NOT valid for hash {:#064x}", hash);
+        }
+
+        eprintln!(
+            "Signature is valid for hash {}",
+            format!("{hash:#064x}").bright_yellow()
+        );
+
+        Ok(())
+    }
+
+    /// Decodes the account address if any, the signed hash, and the signature elements.
+    async fn decode_hash_and_signature<S>(
+        &self,
+        felt_decoder: &FeltDecoder<S>,
+    ) -> Result<(Option<Felt>, Felt, Vec<Felt>)>
+    where
+        S: ChainIdSource + ContractCaller,
+    {
+        let address = match &self.address {
+            Some(address) => Some(
+                felt_decoder
+                    .decode_single_with_addr_fallback(address)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        let hash = match (&self.typed_data, &self.hash) {
+            (Some(typed_data_file), None) => {
+                let typed_data: starknet::core::types::TypedData = serde_json::from_reader(
+                    std::fs::File::open(typed_data_file)?,
+                )
+                .map_err(|err| {
+                    anyhow::anyhow!("invalid typed data {}: {}", typed_data_file.display(), err)
+                })?;
+
+                let address = address.ok_or_else(|| {
+                    anyhow::anyhow!("--address is required for hashing typed data")
+                })?;
+                typed_data.message_hash(address)?
+            }
+            (None, Some(hash)) => felt_decoder.decode_single(hash).await?,
+            // Enforced by clap
+            _ => unreachable!(),
+        };
+
+        let mut signature = vec![];
+        for element in self.signature.iter() {
+            signature.append(&mut felt_decoder.decode(element).await?);
+        }
+
+        Ok((address, hash, signature))
+    }
+}
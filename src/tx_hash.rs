@@ -0,0 +1,321 @@
+use anyhow::Result;
+use serde::Deserialize;
+use starknet::{
+    core::{types::Felt, utils::get_contract_address},
+    macros::{felt, short_string},
+};
+use starknet_crypto::poseidon_hash_many;
+
+const VERSION: Felt = felt!("0x3");
+
+/// Version used for fee estimation and simulation, which is never valid for execution.
+const QUERY_VERSION: Felt = felt!("0x100000000000000000000000000000003");
+
+/// A v3 transaction body in its JSON-RPC representation, holding only the fields that go into
+/// the transaction hash. Other fields, such as `signature` or `transaction_hash`, are ignored.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionBody {
+    Invoke(InvokeBody),
+    Declare(DeclareBody),
+    DeployAccount(DeployAccountBody),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InvokeBody {
+    #[serde(flatten)]
+    common: CommonFields,
+    sender_address: Felt,
+    calldata: Vec<Felt>,
+    account_deployment_data: Vec<Felt>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeclareBody {
+    #[serde(flatten)]
+    common: CommonFields,
+    sender_address: Felt,
+    class_hash: Felt,
+    compiled_class_hash: Felt,
+    account_deployment_data: Vec<Felt>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeployAccountBody {
+    #[serde(flatten)]
+    common: CommonFields,
+    class_hash: Felt,
+    contract_address_salt: Felt,
+    constructor_calldata: Vec<Felt>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommonFields {
+    version: Felt,
+    nonce: Felt,
+    resource_bounds: ResourceBoundsMapping,
+    tip: Felt,
+    paymaster_data: Vec<Felt>,
+    nonce_data_availability_mode: DataAvailabilityMode,
+    fee_data_availability_mode: DataAvailabilityMode,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResourceBoundsMapping {
+    l1_gas: ResourceBounds,
+    l2_gas: ResourceBounds,
+    /// Absent from transactions served by nodes on JSON-RPC versions before 0.8.
+    l1_data_gas: Option<ResourceBounds>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResourceBounds {
+    max_amount: Felt,
+    max_price_per_unit: Felt,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum DataAvailabilityMode {
+    L1,
+    L2,
+}
+
+impl TransactionBody {
+    /// Parses a transaction from its JSON-RPC representation, failing on anything but v3.
+    pub fn from_json(value: serde_json::Value) -> Result<Self> {
+        if let Some(version) = value.get("version").and_then(|version| version.as_str()) {
+            let version = Felt::from_hex(version)
+                .map_err(|_| anyhow::anyhow!("invalid transaction version: {}", version))?;
+            if version != VERSION && version != QUERY_VERSION {
+                anyhow::bail!(
+                    "unsupported transaction version {:#x}: only v3 transactions are supported",
+                    version
+                );
+            }
+        }
+
+        serde_json::from_value(value)
+            .map_err(|err| anyhow::anyhow!("invalid v3 transaction: {}", err))
+    }
+
+    /// Whether the hash has to be computed the way it was before Starknet v0.13.4, which did not
+    /// commit to L1 data gas bounds. Nodes still report empty bounds for such transactions.
+    pub fn may_omit_l1_data_gas(&self) -> bool {
+        match &self.common().resource_bounds.l1_data_gas {
+            Some(bounds) => {
+                bounds.max_amount == Felt::ZERO && bounds.max_price_per_unit == Felt::ZERO
+            }
+            None => true,
+        }
+    }
+
+    /// Computes the transaction hash as specified in SNIP-8. L1 data gas bounds are only
+    /// committed to when `include_l1_data_gas` is set and the bounds are present.
+    pub fn hash(&self, chain_id: Felt, include_l1_data_gas: bool) -> Result<Felt> {
+        let common = self.common();
+
+        let mut elements = vec![
+            match self {
+                Self::Invoke(_) => short_string!("invoke"),
+                Self::Declare(_) => short_string!("declare"),
+                Self::DeployAccount(_) => short_string!("deploy_account"),
+            },
+            common.version,
+            match self {
+                Self::Invoke(tx) => tx.sender_address,
+                Self::Declare(tx) => tx.sender_address,
+                Self::DeployAccount(tx) => get_contract_address(
+                    tx.contract_address_salt,
+                    tx.class_hash,
+                    &tx.constructor_calldata,
+                    Felt::ZERO,
+                ),
+            },
+            common.fee_fields_hash(include_l1_data_gas)?,
+            poseidon_hash_many(&common.paymaster_data),
+            chain_id,
+            common.nonce,
+            common.data_availability_modes(),
+        ];
+
+        match self {
+            Self::Invoke(tx) => {
+                elements.push(poseidon_hash_many(&tx.account_deployment_data));
+                elements.push(poseidon_hash_many(&tx.calldata));
+            }
+            Self::Declare(tx) => {
+                elements.push(poseidon_hash_many(&tx.account_deployment_data));
+                elements.push(tx.class_hash);
+                elements.push(tx.compiled_class_hash);
+            }
+            Self::DeployAccount(tx) => {
+                elements.push(poseidon_hash_many(&tx.constructor_calldata));
+                elements.push(tx.class_hash);
+                elements.push(tx.contract_address_salt);
+            }
+        }
+
+        Ok(poseidon_hash_many(&elements))
+    }
+
+    fn common(&self) -> &CommonFields {
+        match self {
+            Self::Invoke(tx) => &tx.common,
+            Self::Declare(tx) => &tx.common,
+            Self::DeployAccount(tx) => &tx.common,
+        }
+    }
+}
+
+impl CommonFields {
+    fn fee_fields_hash(&self, include_l1_data_gas: bool) -> Result<Felt> {
+        if self.tip > Felt::from(u64::MAX) {
+            anyhow::bail!("tip out of range: {:#x}", self.tip);
+        }
+
+        let mut elements = vec![
+            self.tip,
+            self.resource_bounds
+                .l1_gas
+                .encode(short_string!("L1_GAS"))?,
+            self.resource_bounds
+                .l2_gas
+                .encode(short_string!("L2_GAS"))?,
+        ];
+        if include_l1_data_gas {
+            if let Some(l1_data_gas) = &self.resource_bounds.l1_data_gas {
+                elements.push(l1_data_gas.encode(short_string!("L1_DATA"))?);
+            }
+        }
+
+        Ok(poseidon_hash_many(&elements))
+    }
+
+    /// Both modes packed into a single field element, with the nonce mode in the upper half.
+    fn data_availability_modes(&self) -> Felt {
+        Felt::from(
+            ((self.nonce_data_availability_mode.as_u64()) << 32)
+                + self.fee_data_availability_mode.as_u64(),
+        )
+    }
+}
+
+impl ResourceBounds {
+    /// Packs the bounds of a resource as `resource_name (60 bits) | max_amount (64 bits) |
+    /// max_price_per_unit (128 bits)`.
+    fn encode(&self, resource_name: Felt) -> Result<Felt> {
+        if self.max_amount > Felt::from(u64::MAX) {
+            anyhow::bail!("max_amount out of range: {:#x}", self.max_amount);
+        }
+        if self.max_price_per_unit > Felt::from(u128::MAX) {
+            anyhow::bail!(
+                "max_price_per_unit out of range: {:#x}",
+                self.max_price_per_unit
+            );
+        }
+
+        Ok(resource_name * Felt::TWO.pow(192u32)
+            + self.max_amount * Felt::TWO.pow(128u32)
+            + self.max_price_per_unit)
+    }
+}
+
+impl DataAvailabilityMode {
+    fn as_u64(&self) -> u64 {
+        match self {
+            Self::L1 => 0,
+            Self::L2 => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAINNET: Felt = short_string!("SN_MAIN");
+    const SEPOLIA: Felt = short_string!("SN_SEPOLIA");
+
+    fn invoke_json(l1_data_gas: Option<serde_json::Value>) -> serde_json::Value {
+        let mut resource_bounds = serde_json::json!({
+            "l1_gas": { "max_amount": "0x186a0", "max_price_per_unit": "0x5af3107a4000" },
+            "l2_gas": { "max_amount": "0x0", "max_price_per_unit": "0x0" },
+        });
+        if let Some(l1_data_gas) = l1_data_gas {
+            resource_bounds["l1_data_gas"] = l1_data_gas;
+        }
+
+        serde_json::json!({
+            "type": "INVOKE",
+            "version": "0x3",
+            "sender_address": "0x1234",
+            "calldata": ["0x1", "0x4567", "0x89", "0x2", "0xa", "0x14"],
+            "nonce": "0x7",
+            "resource_bounds": resource_bounds,
+            "tip": "0x0",
+            "paymaster_data": [],
+            "account_deployment_data": [],
+            "nonce_data_availability_mode": "L1",
+            "fee_data_availability_mode": "L1",
+            "signature": ["0x1", "0x2"],
+        })
+    }
+
+    #[test]
+    fn test_invoke_hash() {
+        let tx = TransactionBody::from_json(invoke_json(None)).unwrap();
+        assert!(tx.may_omit_l1_data_gas());
+
+        assert_eq!(
+            tx.hash(MAINNET, false).unwrap(),
+            felt!("0x04b7567b347b657a5e1dae0d0b0793197f51730b0785ece9f24facfa4ce5a5aa")
+        );
+        assert_eq!(
+            tx.hash(SEPOLIA, false).unwrap(),
+            felt!("0x024ae0a2e2f09951a47d41a5f775ac30c1607e41c6b3c546093cbc574367fcd1")
+        );
+    }
+
+    #[test]
+    fn test_invoke_hash_l1_data_gas() {
+        let empty = serde_json::json!({ "max_amount": "0x0", "max_price_per_unit": "0x0" });
+        let tx = TransactionBody::from_json(invoke_json(Some(empty))).unwrap();
+        assert!(tx.may_omit_l1_data_gas());
+        assert_eq!(
+            tx.hash(MAINNET, false).unwrap(),
+            felt!("0x04b7567b347b657a5e1dae0d0b0793197f51730b0785ece9f24facfa4ce5a5aa")
+        );
+
+        let bounds = serde_json::json!({ "max_amount": "0x80", "max_price_per_unit": "0x1" });
+        let tx = TransactionBody::from_json(invoke_json(Some(bounds))).unwrap();
+        assert!(!tx.may_omit_l1_data_gas());
+        assert_ne!(
+            tx.hash(MAINNET, true).unwrap(),
+            tx.hash(MAINNET, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_query_version_accepted() {
+        let mut json = invoke_json(None);
+        json["version"] = "0x100000000000000000000000000000003".into();
+        assert!(TransactionBody::from_json(json).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_transactions() {
+        let mut json = invoke_json(None);
+        json["version"] = "0x1".into();
+        assert!(TransactionBody::from_json(json).is_err());
+
+        let mut json = invoke_json(None);
+        json["tip"] = "0x10000000000000000".into();
+        let tx = TransactionBody::from_json(json).unwrap();
+        assert!(tx.hash(MAINNET, false).is_err());
+
+        let mut json = invoke_json(None);
+        json["resource_bounds"]["l1_gas"]["max_amount"] = "0x10000000000000000".into();
+        let tx = TransactionBody::from_json(json).unwrap();
+        assert!(tx.hash(MAINNET, false).is_err());
+    }
+}
@@ -1,11 +1,11 @@
-use std::{io::Read, time::Duration};
+use std::{collections::HashMap, io::Read, time::Duration};
 
 use anyhow::Result;
 use bigdecimal::{BigDecimal, Zero};
 use colored::Colorize;
 use colored_json::ColoredFormatter;
 use flate2::read::GzDecoder;
-use num_bigint::{BigInt, Sign};
+use num_bigint::{BigInt, BigUint, Sign};
 use num_integer::Integer;
 use regex::Regex;
 use serde::Serialize;
@@ -26,6 +26,10 @@ use starknet::{
     providers::{Provider, ProviderError},
 };
 
+/// The default UDC address: 0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf.
+pub const DEFAULT_UDC_ADDRESS: Felt =
+    felt!("0x041a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf");
+
 pub async fn watch_tx<P>(provider: P, transaction_hash: Felt, poll_interval: Duration) -> Result<()>
 where
     P: Provider,
@@ -88,7 +92,7 @@ where
 
     let mut biguint = match bigint.to_biguint() {
         Some(value) => value,
-        None => anyhow::bail!("too many decimal places"),
+        None => anyhow::bail!("negative value not allowed"),
     };
 
     if exponent < decimals {
@@ -105,6 +109,11 @@ where
         }
     }
 
+    let felt_max = BigUint::from_bytes_be(&Felt::MAX.to_bytes_be());
+    if biguint > felt_max {
+        anyhow::bail!("value out of range");
+    }
+
     Ok(Felt::from_bytes_be_slice(&biguint.to_bytes_be()))
 }
 
@@ -143,6 +152,64 @@ where
     Ok(())
 }
 
+/// Fields in JSON-RPC responses that hold contract addresses.
+const ADDRESS_FIELDS: [&str; 6] = [
+    "address",
+    "caller_address",
+    "contract_address",
+    "from_address",
+    "sender_address",
+    "to_address",
+];
+
+/// Prints the value as JSON, with every known address annotated with its name. Labels are added as
+/// separate `<field>_label` keys so that the original values are left untouched.
+pub fn print_labelled_json<T>(value: &T, labels: &HashMap<Felt, String>) -> Result<()>
+where
+    T: Serialize,
+{
+    let mut value = serde_json::to_value(value)?;
+    label_json_value(&mut value, labels);
+
+    print_colored_json(&value)
+}
+
+fn label_json_value(value: &mut serde_json::Value, labels: &HashMap<Felt, String>) {
+    match value {
+        serde_json::Value::Array(values) => {
+            for value in values.iter_mut() {
+                label_json_value(value, labels);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            let mut found_labels = vec![];
+
+            for (key, value) in map.iter_mut() {
+                match value {
+                    // Felts are always serialized in hex
+                    serde_json::Value::String(string) if ADDRESS_FIELDS.contains(&key.as_str()) => {
+                        if let Some(label) = Felt::from_hex(string)
+                            .ok()
+                            .and_then(|felt| labels.get(&felt))
+                        {
+                            found_labels.push((format!("{key}_label"), label.to_owned()));
+                        }
+                    }
+                    _ => label_json_value(value, labels),
+                }
+            }
+
+            for (key, label) in found_labels.into_iter() {
+                map.insert(key, serde_json::Value::String(label));
+            }
+        }
+        serde_json::Value::Null
+        | serde_json::Value::Bool(_)
+        | serde_json::Value::Number(_)
+        | serde_json::Value::String(_) => {}
+    }
+}
+
 /// Attempts to recover a flattened Sierra class by parsing its ABI string. This works only if the
 /// declared ABI string is a valid JSON representation of Seirra ABI.
 pub fn parse_flattened_sierra_class(class: FlattenedSierraClass) -> Result<SierraClass> {
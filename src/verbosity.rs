@@ -14,6 +14,8 @@ impl VerbosityArgs {
 
         if self.log_traffic {
             builder.filter_module("starknet_providers", LevelFilter::Trace);
+            // Endpoint failovers are logged by Starkli itself
+            builder.filter_module("starkli", LevelFilter::Trace);
         }
 
         builder.init();